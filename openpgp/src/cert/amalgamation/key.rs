@@ -1936,6 +1936,50 @@ impl<'a, P, R, R2> ValidKeyAmalgamation<'a, P, R, R2>
         !(&our_flags & flags.borrow()).is_empty()
     }
 
+    /// Returns whether the key has all of the specified key flags.
+    ///
+    /// If `flags` is empty, this trivially returns `true`.
+    ///
+    /// The key flags are looked up as described in
+    /// [`ValidKeyAmalgamation::key_flags`].
+    ///
+    /// # Examples
+    ///
+    /// Finds keys that may be used for both signing *and*
+    /// certification:
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// for ka in cert.keys().with_policy(p, None) {
+    ///     if ka.has_all_key_flags(KeyFlags::empty()
+    ///        .set_signing()
+    ///        .set_certification())
+    ///     {
+    ///         // `ka` is signing and certification capable.
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ValidKeyAmalgamation::key_flags`]: ValidKeyAmalgamation::key_flags()
+    pub fn has_all_key_flags<F>(&self, flags: F) -> bool
+        where F: Borrow<KeyFlags>
+    {
+        let flags = flags.borrow();
+        let our_flags = self.key_flags().unwrap_or_else(KeyFlags::empty);
+        (&our_flags & flags).normalized_eq(flags)
+    }
+
     /// Returns whether the key is certification capable.
     ///
     /// Note: [Section 12.1 of RFC 4880] says that the primary key is
@@ -1,3 +1,6 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
 use std::fmt;
 use std::convert::TryInto;
 use std::time::SystemTime;
@@ -5,15 +8,84 @@ use std::borrow::Borrow;
 use std::slice;
 
 use crate::{
+    Error,
+    Fingerprint,
     KeyHandle,
     types::RevocationStatus,
+    packet::Key,
     packet::key,
     packet::key::SecretKeyMaterial,
     types::KeyFlags,
+    types::Curve,
+    types::PublicKeyAlgorithm,
+    packet::Signature,
     cert::prelude::*,
     policy::Policy,
 };
 
+/// Returns whether `ka` is a subkey whose binding signature includes
+/// a valid primary key binding signature.
+fn has_valid_backsig(cert: &Cert,
+                     ka: &ErasedKeyAmalgamation<key::PublicParts>)
+    -> bool
+{
+    if ka.primary() {
+        return false;
+    }
+
+    let pk = cert.primary_key().key();
+    let subkey = ka.key().role_as_subordinate();
+    ka.self_signatures().any(|binding| {
+        binding.embedded_signatures().any(|backsig| {
+            backsig.clone().verify_primary_key_binding(pk, subkey).is_ok()
+        })
+    })
+}
+
+/// Returns whether `ka` is alive at time `t` according to its most
+/// recent self-signature created at or before `t`.
+///
+/// If there is no such self-signature, only the key's creation time
+/// is considered.
+fn alive_per_self_signature(ka: &ErasedKeyAmalgamation<key::PublicParts>,
+                            t: SystemTime)
+    -> bool
+{
+    let sig = ka.self_signatures().find(|sig| {
+        sig.signature_creation_time().map(|ct| ct <= t).unwrap_or(false)
+    });
+
+    match sig {
+        Some(sig) => sig.key_alive(ka.key(), t).is_ok(),
+        None => ka.key().creation_time() <= t,
+    }
+}
+
+/// Returns whether `ka` is an RSA key whose modulus is shorter than
+/// `bits` bits.
+fn rsa_bits_below(ka: &ErasedKeyAmalgamation<key::PublicParts>,
+                  bits: usize)
+    -> bool
+{
+    match ka.key().mpis() {
+        crate::crypto::mpi::PublicKey::RSA { n, .. } => n.bits() < bits,
+        _ => false,
+    }
+}
+
+/// Returns whether `ka` is an ECC key on `curve`.
+fn on_curve(ka: &ErasedKeyAmalgamation<key::PublicParts>, curve: &Curve)
+    -> bool
+{
+    use crate::crypto::mpi::PublicKey::*;
+    match ka.key().mpis() {
+        ECDSA { curve: c, .. }
+        | EdDSA { curve: c, .. }
+        | ECDH { curve: c, .. } => c == curve,
+        _ => false,
+    }
+}
+
 /// An iterator over `Key`s.
 ///
 /// An iterator over [`KeyAmalgamation`]s.
@@ -40,7 +112,11 @@ use crate::{
 ///
 /// `KeyAmalgamationIter` follows the builder pattern.  There is no
 /// need to explicitly finalize it: it already implements the
-/// `Iterator` trait.
+/// `Iterator` trait.  Thus, to count or collect the keys that pass
+/// the filters, use [`Iterator::count`] and [`Iterator::collect`].
+///
+/// The primary key, if it passes the filters, is always returned
+/// first, followed by the subkeys in the order of [`Cert::subkeys`].
 ///
 /// A `KeyAmalgamationIter` is returned by [`Cert::keys`].
 ///
@@ -50,6 +126,9 @@ use crate::{
 /// [`KeyAmalgamationIter::secret`]: KeyAmalgamationIter::secret()
 /// [`KeyAmalgamationIter::unencrypted_secret`]: KeyAmalgamationIter::unencrypted_secret()
 /// [`Iterator::filter`]: std::iter::Iterator::filter()
+/// [`Iterator::count`]: std::iter::Iterator::count()
+/// [`Iterator::collect`]: std::iter::Iterator::collect()
+/// [`Cert::subkeys`]: super::super::Cert::subkeys()
 /// [`Cert::keys`]: super::super::Cert::keys()
 pub struct KeyAmalgamationIter<'a, P, R>
     where P: key::KeyParts,
@@ -75,6 +154,25 @@ pub struct KeyAmalgamationIter<'a, P, R>
     // algorithm.
     supported: Option<bool>,
 
+    // If not None, only returns keys that have been revoked at some
+    // point in time.
+    self_revoked: Option<()>,
+
+    // If not None, only returns subkeys with a valid primary key
+    // binding signature.
+    backsig: Option<()>,
+
+    // If not None, only returns keys that are alive at the given
+    // time according to their self-signatures.
+    alive_at: Option<SystemTime>,
+
+    // If not None, only returns RSA keys whose modulus has fewer
+    // than this many bits.
+    rsa_bits_below: Option<usize>,
+
+    // If not None, only returns ECC keys on the given curve.
+    curve: Option<Curve>,
+
     _p: std::marker::PhantomData<P>,
     _r: std::marker::PhantomData<R>,
 }
@@ -93,6 +191,11 @@ impl<'a, P, R> fmt::Debug for KeyAmalgamationIter<'a, P, R>
             .field("unencrypted_secret", &self.unencrypted_secret)
             .field("key_handles", &self.key_handles)
             .field("supported", &self.supported)
+            .field("self_revoked", &self.self_revoked)
+            .field("backsig", &self.backsig)
+            .field("alive_at", &self.alive_at)
+            .field("rsa_bits_below", &self.rsa_bits_below)
+            .field("curve", &self.curve)
             .finish()
     }
 }
@@ -181,6 +284,42 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
                 }
             }
 
+            if let Some(()) = self.self_revoked {
+                if ka.self_revocations().next().is_none() {
+                    t!("Key never revoked... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(()) = self.backsig {
+                if ! has_valid_backsig(cert, &ka) {
+                    t!("No valid primary key binding signature... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(t) = self.alive_at {
+                if ! alive_per_self_signature(&ka, t) {
+                    t!("Not alive according to self-signatures... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(bits) = self.rsa_bits_below {
+                if ! rsa_bits_below(&ka, bits) {
+                    t!("Not an RSA key with less than {} bits... skipping.",
+                       bits);
+                    continue;
+                }
+            }
+
+            if let Some(curve) = self.curve.as_ref() {
+                if ! on_curve(&ka, curve) {
+                    t!("Not an ECC key on {}... skipping.", curve);
+                    continue;
+                }
+            }
+
             if let Some(want_secret) = self.secret {
                 if ka.key().has_secret() {
                     // We have a secret.
@@ -233,6 +372,11 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
             unencrypted_secret: None,
             key_handles: None,
             supported: None,
+            self_revoked: None,
+            backsig: None,
+            alive_at: None,
+            rsa_bits_below: None,
+            curve: None,
 
             _p: std::marker::PhantomData,
             _r: std::marker::PhantomData,
@@ -269,6 +413,11 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
             unencrypted_secret: self.unencrypted_secret,
             key_handles: self.key_handles,
             supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
 
             _p: std::marker::PhantomData,
             _r: std::marker::PhantomData,
@@ -305,6 +454,60 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
             unencrypted_secret: Some(true),
             key_handles: self.key_handles,
             supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
+
+            _p: std::marker::PhantomData,
+            _r: std::marker::PhantomData,
+        }
+    }
+
+    /// Changes the iterator to only return keys with usable secret
+    /// key material.
+    ///
+    /// This is equivalent to calling both [`secret`] and
+    /// [`unencrypted_secret`]: only keys whose secret key material
+    /// is present and not encrypted are returned.
+    ///
+    /// [`secret`]: KeyAmalgamationIter::secret()
+    /// [`unencrypted_secret`]: KeyAmalgamationIter::unencrypted_secret()
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// for ka in cert.keys().usable_secret() {
+    ///     let mut keypair = ka.key().clone().into_keypair()?;
+    ///     // Use it.
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn usable_secret(self) -> KeyAmalgamationIter<'a, key::SecretParts, R> {
+        KeyAmalgamationIter {
+            cert: self.cert,
+            primary: self.primary,
+            subkey_iter: self.subkey_iter,
+
+            // The filters.
+            secret: Some(true),
+            unencrypted_secret: Some(true),
+            key_handles: self.key_handles,
+            supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
 
             _p: std::marker::PhantomData,
             _r: std::marker::PhantomData,
@@ -429,11 +632,18 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
         self
     }
 
-    /// Changes the iterator to only return subkeys.
+    /// Changes the iterator to only return keys that have been
+    /// revoked.
     ///
-    /// This function also changes the return type.  Instead of the
-    /// iterator returning a [`ErasedKeyAmalgamation`], it returns a
-    /// [`SubordinateKeyAmalgamation`].
+    /// A key is returned if its binding has at least one
+    /// self-revocation.  This is a historical check, not a
+    /// point-in-time check: neither the revocation's creation time
+    /// nor any policy is considered, and a soft revocation that was
+    /// later superseded by a new binding signature still counts.  To
+    /// get a key's revocation status at a given time, use
+    /// [`ValidKeyAmalgamationIter::revoked`] instead.
+    ///
+    /// This filter is preserved by [`KeyAmalgamationIter::with_policy`].
     ///
     /// # Examples
     ///
@@ -441,123 +651,634 @@ impl<'a, P, R> KeyAmalgamationIter<'a, P, R>
     /// # use sequoia_openpgp as openpgp;
     /// # use openpgp::Result;
     /// # use openpgp::cert::prelude::*;
-    /// #
     /// # fn main() -> Result<()> {
-    /// #      let (cert, _) = CertBuilder::new()
-    /// #          .add_signing_subkey()
-    /// #          .add_certification_subkey()
-    /// #          .add_transport_encryption_subkey()
-    /// #          .add_storage_encryption_subkey()
-    /// #          .add_authentication_subkey()
-    /// #          .generate()?;
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
     /// # let mut i = 0;
-    /// for ka in cert.keys().subkeys() {
-    ///     // Use it.
-    ///     assert!(! ka.primary());
+    /// for ka in cert.keys().revoked() {
+    ///     println!("{} was revoked at some point", ka.fingerprint());
     /// #   i += 1;
     /// }
-    /// # assert_eq!(i, 5);
-    /// #     Ok(())
-    /// # }
+    /// # assert_eq!(i, 0);
+    /// # Ok(()) }
     /// ```
     ///
-    pub fn subkeys(self) -> KeyAmalgamationIter<'a, P, key::SubordinateRole> {
-        KeyAmalgamationIter {
-            cert: self.cert,
-            primary: true,
-            subkey_iter: self.subkey_iter,
-
-            // The filters.
-            secret: self.secret,
-            unencrypted_secret: self.unencrypted_secret,
-            key_handles: self.key_handles,
-            supported: self.supported,
+    /// [`ValidKeyAmalgamationIter::revoked`]: ValidKeyAmalgamationIter::revoked()
+    /// [`KeyAmalgamationIter::with_policy`]: super::ValidateAmalgamation
+    pub fn revoked(mut self) -> Self {
+        self.self_revoked = Some(());
+        self
+    }
 
-            _p: std::marker::PhantomData,
-            _r: std::marker::PhantomData,
+    /// Collects the keys into a map indexed by their fingerprints.
+    ///
+    /// This consumes the iterator, and returns the keys that pass
+    /// the filters.  If two keys have the same fingerprint, the one
+    /// that comes first is kept.  Since the primary key is returned
+    /// first, it takes precedence over any subkey.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// let keys = cert.keys().into_fingerprint_map();
+    /// assert!(keys.contains_key(&cert.fingerprint()));
+    /// # assert_eq!(keys.len(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn into_fingerprint_map(mut self)
+        -> BTreeMap<Fingerprint, Key<P, key::UnspecifiedRole>>
+    {
+        let mut map = BTreeMap::new();
+        while let Some(ka) = self.next_common() {
+            map.entry(ka.key().fingerprint()).or_insert_with(|| {
+                // This is safe by construction: next_common only
+                // returns keys that can be correctly converted.
+                P::convert_key(ka.key().clone().parts_into_unspecified())
+                    .expect("filtered")
+            });
         }
+        map
     }
 
-    /// Changes the iterator to only return valid `Key`s.
+    /// Changes the iterator to return clones of the keys.
     ///
-    /// If `time` is None, then the current time is used.
+    /// This is a shorthand for mapping each [`KeyAmalgamation`] to
+    /// a clone of its [`Key`], for when the binding signatures are
+    /// not needed.  The keys that pass the filters are returned in
+    /// the same order, i.e. the primary key comes first.
     ///
-    /// This also makes a number of additional filters like [`alive`]
-    /// and [`revoked`] available.
+    /// # Examples
     ///
-    /// Refer to the [`ValidateAmalgamation`] trait for a definition
-    /// of a valid component.
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// let keys: Vec<_> = cert.keys().secret().keys_cloned().collect();
+    /// assert_eq!(keys[0].fingerprint(), cert.fingerprint());
+    /// assert!(keys.iter().all(|k| k.has_secret()));
+    /// # assert_eq!(keys.len(), 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn keys_cloned(mut self)
+        -> impl Iterator<Item = Key<P, key::UnspecifiedRole>> + 'a
+        where P: 'a, R: 'a,
+    {
+        std::iter::from_fn(move || {
+            let ka = self.next_common()?;
+            // This is safe by construction: next_common only returns
+            // keys that can be correctly converted.
+            Some(P::convert_key(ka.key().clone().parts_into_unspecified())
+                 .expect("filtered"))
+        })
+    }
+
+    /// Changes the iterator to only return subkeys that are
+    /// cross-certified.
+    ///
+    /// A subkey is returned if at least one of its binding
+    /// signatures contains an embedded [Primary Key Binding
+    /// signature] (a so-called backsig) that can be verified using
+    /// the subkey.  Signing-capable subkeys are required to have
+    /// one; encryption-only subkeys usually don't.  The primary key
+    /// is never returned.
+    ///
+    /// This filter doesn't consider any policy or reference time, and
+    /// it verifies the embedded signatures, which involves public key
+    /// operations.
+    ///
+    /// This filter is preserved by [`KeyAmalgamationIter::with_policy`].
+    ///
+    /// [Primary Key Binding signature]: https://tools.ietf.org/html/rfc4880#section-5.2.1
+    /// [`KeyAmalgamationIter::with_policy`]: super::ValidateAmalgamation
     ///
     /// # Examples
     ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) = CertBuilder::new()
+    /// #         .add_signing_subkey()
+    /// #         .add_transport_encryption_subkey()
+    /// #         .generate()?;
+    /// # let mut i = 0;
+    /// for ka in cert.keys().with_valid_backsig() {
+    ///     // Only the signing subkey.
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 1);
+    /// #     Ok(())
+    /// # }
     /// ```
+    pub fn with_valid_backsig(mut self) -> Self {
+        self.backsig = Some(());
+        self
+    }
+
+    /// Changes the iterator to only return keys that are alive at the
+    /// specified time.
+    ///
+    /// A key is considered alive if it was created at or before `at`,
+    /// and the most recent self-signature that was created at or
+    /// before `at` doesn't say that the key has expired by then.
+    ///
+    /// This is a coarser check than [`ValidKeyAmalgamationIter::alive`]:
+    /// the self-signatures are neither verified nor checked against a
+    /// policy, and revocations are ignored.  Further, the primary
+    /// key's expiration time is only taken from its direct key
+    /// signatures, and a subkey is considered alive even if the
+    /// certificate has expired.  Use it when a cheap approximation is
+    /// good enough, e.g., for display purposes.
+    ///
+    /// This filter is preserved by [`KeyAmalgamationIter::with_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
     /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
     /// # use openpgp::cert::prelude::*;
-    /// use openpgp::policy::StandardPolicy;
-    /// #
-    /// # fn main() -> openpgp::Result<()> {
-    /// let p = &StandardPolicy::new();
+    /// use std::time::SystemTime;
     ///
+    /// # fn main() -> Result<()> {
     /// #     let (cert, _) =
     /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
     /// #         .generate()?;
-    /// #     let fpr = cert.fingerprint();
-    /// // Iterate over all valid User Attributes.
-    /// for ka in cert.keys().with_policy(p, None) {
-    ///     // ka is a `ValidKeyAmalgamation`, specifically, an
-    ///     // `ValidErasedKeyAmalgamation`.
+    /// # let mut i = 0;
+    /// for ka in cert.keys().alive_at(SystemTime::now()) {
+    ///     println!("{} has not expired", ka.fingerprint());
+    /// #   i += 1;
     /// }
+    /// # assert_eq!(i, 3);
     /// #     Ok(())
     /// # }
     /// ```
     ///
-    /// [`ValidateAmalgamation`]: super::ValidateAmalgamation
-    /// [`alive`]: ValidKeyAmalgamationIter::alive()
-    /// [`revoked`]: ValidKeyAmalgamationIter::revoked()
-    pub fn with_policy<T>(self, policy: &'a dyn Policy, time: T)
-        -> ValidKeyAmalgamationIter<'a, P, R>
-        where T: Into<Option<SystemTime>>
+    /// [`ValidKeyAmalgamationIter::alive`]: ValidKeyAmalgamationIter::alive()
+    /// [`KeyAmalgamationIter::with_policy`]: super::ValidateAmalgamation
+    pub fn alive_at<T>(mut self, at: T) -> Self
+        where T: Into<SystemTime>
     {
-        ValidKeyAmalgamationIter {
-            cert: self.cert,
-            primary: self.primary,
-            subkey_iter: self.subkey_iter,
-
-            policy,
-            time: time.into().unwrap_or_else(crate::now),
-
-            // The filters.
-            secret: self.secret,
-            unencrypted_secret: self.unencrypted_secret,
-            key_handles: self.key_handles,
-            supported: self.supported,
-            flags: None,
-            alive: None,
-            revoked: None,
+        self.alive_at = Some(at.into());
+        self
+    }
 
-            _p: self._p,
-            _r: self._r,
-        }
+    /// Changes the iterator to only return RSA keys whose modulus is
+    /// shorter than `bits` bits.
+    ///
+    /// Keys using other algorithms are skipped.  This is useful to
+    /// find weak RSA keys, e.g., those with less than 2048 bits.
+    ///
+    /// This filter is preserved by [`KeyAmalgamationIter::with_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) = CertBuilder::new()
+    /// #         .set_cipher_suite(CipherSuite::RSA2k)
+    /// #         .add_signing_subkey()
+    /// #         .generate()?;
+    /// for ka in cert.keys().rsa_bits_below(3072) {
+    ///     println!("{} is a weak RSA key", ka.fingerprint());
+    /// }
+    /// # assert_eq!(cert.keys().rsa_bits_below(3072).count(), 2);
+    /// # assert_eq!(cert.keys().rsa_bits_below(2048).count(), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`KeyAmalgamationIter::with_policy`]: super::ValidateAmalgamation
+    pub fn rsa_bits_below(mut self, bits: usize) -> Self {
+        self.rsa_bits_below = Some(bits);
+        self
     }
-}
 
-/// An iterator over valid `Key`s.
-///
-/// An iterator over [`ValidKeyAmalgamation`]s.
-///
-/// A `ValidKeyAmalgamationIter` is a [`KeyAmalgamationIter`]
-/// that includes a [`Policy`] and a reference time, which it firstly
-/// uses to only return valid `Key`s.  (For a definition of valid
-/// keys, see the documentation for [`ValidateAmalgamation`].)
-///
-/// A `ValidKeyAmalgamationIter` also provides additional
-/// filters based on information available in the `Key`s' binding
-/// signatures.  For instance, [`ValidKeyAmalgamationIter::revoked`]
-/// filters the returned `Key`s by whether or not they are revoked.
-/// And, [`ValidKeyAmalgamationIter::alive`] changes the iterator to
-/// only return `Key`s that are live.
-///
-/// `ValidKeyAmalgamationIter` follows the builder pattern.  But,
+    /// Changes the iterator to only return ECC keys on `curve`.
+    ///
+    /// Keys not using elliptic curve cryptography are skipped.  This
+    /// is useful to find keys on a deprecated curve.
+    ///
+    /// This filter is preserved by [`KeyAmalgamationIter::with_policy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::types::Curve;
+    ///
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) = CertBuilder::new()
+    /// #         .set_cipher_suite(CipherSuite::P256)
+    /// #         .add_signing_subkey()
+    /// #         .generate()?;
+    /// for ka in cert.keys().with_curve(Curve::NistP256) {
+    ///     println!("{} uses NIST P-256", ka.fingerprint());
+    /// }
+    /// # assert_eq!(cert.keys().with_curve(Curve::NistP256).count(), 2);
+    /// # assert_eq!(cert.keys().with_curve(Curve::Ed25519).count(), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`KeyAmalgamationIter::with_policy`]: super::ValidateAmalgamation
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = Some(curve);
+        self
+    }
+
+    /// Changes the iterator to only return the keys' fingerprints.
+    ///
+    /// This is a convenient way to inspect the keys without holding
+    /// on to the amalgamations.  Combined with
+    /// [`Iterator::peekable`], it can be used to look at the next
+    /// key's fingerprint without advancing the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) = CertBuilder::new()
+    /// #         .add_signing_subkey()
+    /// #         .generate()?;
+    /// let mut fprs = cert.keys().fingerprints().peekable();
+    /// assert_eq!(fprs.peek(), Some(&cert.fingerprint()));
+    /// assert_eq!(fprs.next(), Some(cert.fingerprint()));
+    /// # assert_eq!(fprs.count(), 1);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn fingerprints<R2>(self) -> impl Iterator<Item=Fingerprint> + 'a
+        where Self: Iterator<Item=KeyAmalgamation<'a, P, R, R2>> + 'a,
+              P: 'a,
+              R: 'a,
+    {
+        self.map(|ka| ka.fingerprint())
+    }
+
+    /// Returns the first key that passes the filters.
+    ///
+    /// This is equivalent to calling `next()`, but consumes the
+    /// iterator, and makes the intent clearer than the commonly used
+    /// `nth(0)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// #     let (cert, _) = CertBuilder::new()
+    /// #         .add_signing_subkey()
+    /// #         .generate()?;
+    /// let subkey = cert.keys().subkeys().first()
+    ///     .expect("have a subkey");
+    /// # assert!(! subkey.primary());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn first<R2>(mut self) -> Option<KeyAmalgamation<'a, P, R, R2>>
+        where Self: Iterator<Item=KeyAmalgamation<'a, P, R, R2>>,
+    {
+        self.next()
+    }
+
+    /// Returns whether any key that passes the filters has secret
+    /// key material.
+    ///
+    /// The secret key material may be encrypted.  This consumes the
+    /// iterator, and stops at the first key with secret key
+    /// material.  `cert.keys().any_secret()` is equivalent to
+    /// [`Cert::is_tsk`].
+    ///
+    /// [`Cert::is_tsk`]: crate::Cert::is_tsk()
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// let (cert, _) = CertBuilder::new()
+    ///     .add_signing_subkey()
+    ///     .generate()?;
+    /// assert!(cert.keys().any_secret());
+    ///
+    /// let cert = cert.strip_secret_key_material();
+    /// assert!(! cert.keys().any_secret());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn any_secret<R2>(mut self) -> bool
+        where Self: Iterator<Item=KeyAmalgamation<'a, P, R, R2>>,
+              P: 'a,
+              R: 'a,
+    {
+        self.any(|ka| ka.key().has_secret())
+    }
+
+    /// Returns whether all keys that pass the filters have secret key
+    /// material.
+    ///
+    /// The secret key material may be encrypted.  This consumes the
+    /// iterator, and stops at the first key without secret key
+    /// material.  If no key passes the filters, this returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// # fn main() -> Result<()> {
+    /// let (cert, _) = CertBuilder::new()
+    ///     .add_signing_subkey()
+    ///     .generate()?;
+    /// assert!(cert.keys().all_secret());
+    ///
+    /// let cert = cert.strip_secret_key_material();
+    /// assert!(! cert.keys().all_secret());
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn all_secret<R2>(mut self) -> bool
+        where Self: Iterator<Item=KeyAmalgamation<'a, P, R, R2>>,
+              P: 'a,
+              R: 'a,
+    {
+        self.all(|ka| ka.key().has_secret())
+    }
+
+    /// Changes the iterator to only return subkeys.
+    ///
+    /// This function also changes the return type.  Instead of the
+    /// iterator returning a [`ErasedKeyAmalgamation`], it returns a
+    /// [`SubordinateKeyAmalgamation`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::Result;
+    /// # use openpgp::cert::prelude::*;
+    /// #
+    /// # fn main() -> Result<()> {
+    /// #      let (cert, _) = CertBuilder::new()
+    /// #          .add_signing_subkey()
+    /// #          .add_certification_subkey()
+    /// #          .add_transport_encryption_subkey()
+    /// #          .add_storage_encryption_subkey()
+    /// #          .add_authentication_subkey()
+    /// #          .generate()?;
+    /// # let mut i = 0;
+    /// for ka in cert.keys().subkeys() {
+    ///     // Use it.
+    ///     assert!(! ka.primary());
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 5);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn subkeys(self) -> KeyAmalgamationIter<'a, P, key::SubordinateRole> {
+        KeyAmalgamationIter {
+            cert: self.cert,
+            primary: true,
+            subkey_iter: self.subkey_iter,
+
+            // The filters.
+            secret: self.secret,
+            unencrypted_secret: self.unencrypted_secret,
+            key_handles: self.key_handles,
+            supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
+
+            _p: std::marker::PhantomData,
+            _r: std::marker::PhantomData,
+        }
+    }
+
+    /// Changes the iterator to only return valid `Key`s.
+    ///
+    /// If `time` is None, then the current time is used.
+    ///
+    /// This also makes a number of additional filters like [`alive`]
+    /// and [`revoked`] available.
+    ///
+    /// Refer to the [`ValidateAmalgamation`] trait for a definition
+    /// of a valid component.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    /// #
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// #     let fpr = cert.fingerprint();
+    /// // Iterate over all valid User Attributes.
+    /// for ka in cert.keys().with_policy(p, None) {
+    ///     // ka is a `ValidKeyAmalgamation`, specifically, an
+    ///     // `ValidErasedKeyAmalgamation`.
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The policy and the reference time are independent.  To
+    /// evaluate the keys at a specific point in time, pass it
+    /// explicitly:
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use std::time::{Duration, SystemTime};
+    /// use openpgp::policy::StandardPolicy;
+    /// #
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .set_creation_time(SystemTime::now()
+    /// #                            - Duration::new(3600, 0))
+    /// #         .generate()?;
+    /// let now = SystemTime::now();
+    /// assert_eq!(cert.keys().with_policy(p, now).count(), 3);
+    ///
+    /// // Before the certificate was created, no key is valid.
+    /// let then = now - Duration::new(2 * 3600, 0);
+    /// assert_eq!(cert.keys().with_policy(p, then).count(), 0);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ValidateAmalgamation`]: super::ValidateAmalgamation
+    /// [`alive`]: ValidKeyAmalgamationIter::alive()
+    /// [`revoked`]: ValidKeyAmalgamationIter::revoked()
+    pub fn with_policy<T>(self, policy: &'a dyn Policy, time: T)
+        -> ValidKeyAmalgamationIter<'a, P, R>
+        where T: Into<Option<SystemTime>>
+    {
+        ValidKeyAmalgamationIter {
+            cert: self.cert,
+            primary: self.primary,
+            subkey_iter: self.subkey_iter,
+
+            policy,
+            time: time.into().unwrap_or_else(crate::now),
+
+            // The filters.
+            secret: self.secret,
+            unencrypted_secret: self.unencrypted_secret,
+            key_handles: self.key_handles,
+            supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
+            flags: None,
+            flags_exact: None,
+            alive: None,
+            expired: None,
+            revoked: None,
+            pk_algo_filter: None,
+            binding_filter: None,
+
+            _p: self._p,
+            _r: self._r,
+        }
+    }
+
+    /// Returns keys that can currently be used to make
+    /// certifications.
+    ///
+    /// This is a shorthand for:
+    ///
+    /// ```text
+    /// self.with_policy(policy, None).for_certification().alive().revoked(false)
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new().generate()?;
+    /// let ka = cert.keys().certification_capable_now(p).next()
+    ///     .expect("the primary key can certify");
+    /// # assert!(ka.primary());
+    /// # Ok(()) }
+    /// ```
+    pub fn certification_capable_now(self, policy: &'a dyn Policy)
+        -> ValidKeyAmalgamationIter<'a, P, R>
+    {
+        self.with_policy(policy, None)
+            .for_certification().alive().revoked(false)
+    }
+
+    /// Returns keys that can currently be used to make signatures.
+    ///
+    /// This is a shorthand for:
+    ///
+    /// ```text
+    /// self.with_policy(policy, None).for_signing().alive().revoked(false)
+    /// ```
+    ///
+    /// See [`KeyAmalgamationIter::certification_capable_now`] for an
+    /// example.
+    ///
+    /// [`KeyAmalgamationIter::certification_capable_now`]: KeyAmalgamationIter::certification_capable_now()
+    pub fn signing_capable_now(self, policy: &'a dyn Policy)
+        -> ValidKeyAmalgamationIter<'a, P, R>
+    {
+        self.with_policy(policy, None)
+            .for_signing().alive().revoked(false)
+    }
+
+    /// Returns keys that can currently be used to encrypt data.
+    ///
+    /// Keys that are marked for either transport or storage
+    /// encryption are returned.  This is a shorthand for:
+    ///
+    /// ```text
+    /// self.with_policy(policy, None)
+    ///     .for_transport_encryption().for_storage_encryption()
+    ///     .alive().revoked(false)
+    /// ```
+    ///
+    /// See [`KeyAmalgamationIter::certification_capable_now`] for an
+    /// example.
+    ///
+    /// [`KeyAmalgamationIter::certification_capable_now`]: KeyAmalgamationIter::certification_capable_now()
+    pub fn encryption_capable_now(self, policy: &'a dyn Policy)
+        -> ValidKeyAmalgamationIter<'a, P, R>
+    {
+        self.with_policy(policy, None)
+            .for_transport_encryption().for_storage_encryption()
+            .alive().revoked(false)
+    }
+}
+
+/// An iterator over valid `Key`s.
+///
+/// An iterator over [`ValidKeyAmalgamation`]s.
+///
+/// A `ValidKeyAmalgamationIter` is a [`KeyAmalgamationIter`]
+/// that includes a [`Policy`] and a reference time, which it firstly
+/// uses to only return valid `Key`s.  (For a definition of valid
+/// keys, see the documentation for [`ValidateAmalgamation`].)
+///
+/// A `ValidKeyAmalgamationIter` also provides additional
+/// filters based on information available in the `Key`s' binding
+/// signatures.  For instance, [`ValidKeyAmalgamationIter::revoked`]
+/// filters the returned `Key`s by whether or not they are revoked.
+/// And, [`ValidKeyAmalgamationIter::alive`] changes the iterator to
+/// only return `Key`s that are live.
+///
+/// `ValidKeyAmalgamationIter` follows the builder pattern.  But,
 /// there is no need to explicitly finalize it: it already implements
 /// the `Iterator` trait.
 ///
@@ -649,16 +1370,51 @@ pub struct ValidKeyAmalgamationIter<'a, P, R>
     // algorithm.
     supported: Option<bool>,
 
+    // If not None, only returns keys that have been revoked at some
+    // point in time.
+    self_revoked: Option<()>,
+
+    // If not None, only returns subkeys with a valid primary key
+    // binding signature.
+    backsig: Option<()>,
+
+    // If not None, only returns keys that are alive at the given
+    // time according to their self-signatures.
+    alive_at: Option<SystemTime>,
+
+    // If not None, only returns RSA keys whose modulus has fewer
+    // than this many bits.
+    rsa_bits_below: Option<usize>,
+
+    // If not None, only returns ECC keys on the given curve.
+    curve: Option<Curve>,
+
     // If not None, only returns keys with the specified flags.
     flags: Option<KeyFlags>,
 
+    // If not None, only returns keys with all of the specified flags.
+    flags_exact: Option<KeyFlags>,
+
     // If not None, filters by whether a key is alive at time `t`.
     alive: Option<()>,
 
+    // If not None, only returns keys that are expired at time `t`.
+    expired: Option<()>,
+
     // If not None, filters by whether the key is revoked or not at
     // time `t`.
     revoked: Option<bool>,
 
+    // If not None, only returns keys whose public key algorithm
+    // satisfies the predicate.
+    pk_algo_filter: Option<Box<dyn Fn(PublicKeyAlgorithm) -> bool
+                               + Send + Sync + 'a>>,
+
+    // If not None, only returns keys whose binding signature
+    // satisfies the predicate.
+    binding_filter: Option<Box<dyn Fn(&Signature) -> bool
+                               + Send + Sync + 'a>>,
+
     _p: std::marker::PhantomData<P>,
     _r: std::marker::PhantomData<R>,
 }
@@ -679,9 +1435,18 @@ impl<'a, P, R> fmt::Debug for ValidKeyAmalgamationIter<'a, P, R>
             .field("unencrypted_secret", &self.unencrypted_secret)
             .field("key_handles", &self.key_handles)
             .field("supported", &self.supported)
+            .field("self_revoked", &self.self_revoked)
+            .field("backsig", &self.backsig)
+            .field("alive_at", &self.alive_at)
+            .field("rsa_bits_below", &self.rsa_bits_below)
+            .field("curve", &self.curve)
             .field("flags", &self.flags)
+            .field("flags_exact", &self.flags_exact)
             .field("alive", &self.alive)
+            .field("expired", &self.expired)
             .field("revoked", &self.revoked)
+            .field("pk_algo_filter", &self.pk_algo_filter.is_some())
+            .field("binding_filter", &self.binding_filter.is_some())
             .finish()
     }
 }
@@ -797,6 +1562,42 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
                 }
             }
 
+            if let Some(()) = self.self_revoked {
+                if ka.self_revocations().next().is_none() {
+                    t!("Key never revoked... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(()) = self.backsig {
+                if ! has_valid_backsig(cert, &ka) {
+                    t!("No valid primary key binding signature... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(t) = self.alive_at {
+                if ! alive_per_self_signature(&ka, t) {
+                    t!("Not alive according to self-signatures... skipping.");
+                    continue;
+                }
+            }
+
+            if let Some(bits) = self.rsa_bits_below {
+                if ! rsa_bits_below(&ka, bits) {
+                    t!("Not an RSA key with less than {} bits... skipping.",
+                       bits);
+                    continue;
+                }
+            }
+
+            if let Some(curve) = self.curve.as_ref() {
+                if ! on_curve(&ka, curve) {
+                    t!("Not an ECC key on {}... skipping.", curve);
+                    continue;
+                }
+            }
+
             if let Some(flags) = self.flags.as_ref() {
                 if !ka.has_any_key_flag(flags) {
                     t!("Have flags: {:?}, want flags: {:?}... skipping.",
@@ -805,6 +1606,14 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
                 }
             }
 
+            if let Some(flags) = self.flags_exact.as_ref() {
+                if !ka.has_all_key_flags(flags) {
+                    t!("Have flags: {:?}, want all of: {:?}... skipping.",
+                       ka.key_flags(), flags);
+                    continue;
+                }
+            }
+
             if let Some(()) = self.alive {
                 if let Err(err) = ka.alive() {
                     t!("Key not alive: {:?}", err);
@@ -812,6 +1621,23 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
                 }
             }
 
+            if let Some(()) = self.expired {
+                match ka.alive() {
+                    Ok(()) => {
+                        t!("Key alive... skipping.");
+                        continue;
+                    },
+                    Err(err) => match err.downcast_ref::<Error>() {
+                        Some(Error::Expired(_)) => (),
+                        _ => {
+                            t!("Key not alive, but not expired: {:?}... skipping.",
+                               err);
+                            continue;
+                        },
+                    },
+                }
+            }
+
             if let Some(want_revoked) = self.revoked {
                 if let RevocationStatus::Revoked(_) = ka.revocation_status() {
                     // The key is definitely revoked.
@@ -828,6 +1654,21 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
                 }
             }
 
+            if let Some(filter) = self.pk_algo_filter.as_ref() {
+                if ! filter(key.pk_algo()) {
+                    t!("PK algo {} rejected by filter... skipping.",
+                       key.pk_algo());
+                    continue;
+                }
+            }
+
+            if let Some(filter) = self.binding_filter.as_ref() {
+                if ! filter(ka.binding_signature()) {
+                    t!("Binding signature rejected by filter... skipping.");
+                    continue;
+                }
+            }
+
             if let Some(want_secret) = self.secret {
                 if key.has_secret() {
                     // We have a secret.
@@ -915,27 +1756,94 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
     /// #   let mut i = 0;
     /// for ka in cert.keys()
     ///     .with_policy(p, None)
-    ///     .key_flags(KeyFlags::empty()
-    ///         .set_transport_encryption()
-    ///         .set_storage_encryption())
+    ///     .key_flags(KeyFlags::empty()
+    ///         .set_transport_encryption()
+    ///         .set_storage_encryption())
+    /// {
+    ///     // Valid encryption-capable keys.
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 2);
+    /// # Ok(()) }
+    /// ```
+    ///
+    ///   [Section 12.1 of RFC 4880]: https://tools.ietf.org/html/rfc4880#section-5.2.3.21
+    ///   [`ValidKeyAmalgamation::key_flags`]: ValidKeyAmalgamation::key_flags()
+    pub fn key_flags<F>(mut self, flags: F) -> Self
+        where F: Borrow<KeyFlags>
+    {
+        let flags = flags.borrow();
+        if let Some(flags_old) = self.flags {
+            self.flags = Some(flags | &flags_old);
+        } else {
+            self.flags = Some(flags.clone());
+        }
+        self
+    }
+
+    /// Returns keys that have all of the flags specified in `flags`.
+    ///
+    /// Unlike [`key_flags`], which returns keys that have *at least
+    /// one* of the requested flags, this filter only returns keys
+    /// that have *every* requested flag set.  For instance, asking
+    /// for signing and certification returns a key that is marked
+    /// as both signing and certification capable, but not a key
+    /// that is only signing capable.
+    ///
+    /// If you call this function multiple times, the *union* of the
+    /// values is used, i.e., a key must have all of the flags from
+    /// all of the calls.  This filter is independent of
+    /// [`key_flags`]; if both are used, a key has to satisfy both.
+    ///
+    /// As with [`key_flags`], this function only reflects what is
+    /// stored in the `Key Flags` subpacket; the primary key is not
+    /// implicitly considered to be certification capable.
+    ///
+    /// The key flags are looked up as described in
+    /// [`ValidKeyAmalgamation::key_flags`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .add_subkey(KeyFlags::empty()
+    /// #                   .set_signing()
+    /// #                   .set_certification(), None, None)
+    /// #       .generate()?;
+    /// #   let mut i = 0;
+    /// for ka in cert.keys()
+    ///     .with_policy(p, None)
+    ///     .key_flags_exact(KeyFlags::empty()
+    ///         .set_signing()
+    ///         .set_certification())
     /// {
-    ///     // Valid encryption-capable keys.
+    ///     // Valid keys that are both signing and certification
+    ///     // capable.
     /// #   i += 1;
     /// }
-    /// # assert_eq!(i, 2);
+    /// # assert_eq!(i, 1);
     /// # Ok(()) }
     /// ```
     ///
-    ///   [Section 12.1 of RFC 4880]: https://tools.ietf.org/html/rfc4880#section-5.2.3.21
+    ///   [`key_flags`]: ValidKeyAmalgamationIter::key_flags()
     ///   [`ValidKeyAmalgamation::key_flags`]: ValidKeyAmalgamation::key_flags()
-    pub fn key_flags<F>(mut self, flags: F) -> Self
+    pub fn key_flags_exact<F>(mut self, flags: F) -> Self
         where F: Borrow<KeyFlags>
     {
         let flags = flags.borrow();
-        if let Some(flags_old) = self.flags {
-            self.flags = Some(flags | &flags_old);
+        if let Some(flags_old) = self.flags_exact {
+            self.flags_exact = Some(flags | &flags_old);
         } else {
-            self.flags = Some(flags.clone());
+            self.flags_exact = Some(flags.clone());
         }
         self
     }
@@ -1215,6 +2123,51 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
         self
     }
 
+    /// Returns keys that are expired.
+    ///
+    /// This is the inverse of [`alive`]: it returns keys for which
+    /// [`ValidKeyAmalgamation::alive`] fails because the key has
+    /// expired.  Keys that are not alive for other reasons, e.g.,
+    /// because they were created after the reference time, are not
+    /// returned.
+    ///
+    /// Note: a subkey is considered expired if the certificate is
+    /// expired, even if the subkey's own binding signature doesn't
+    /// set an expiration time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_transport_encryption_subkey()
+    /// #       .generate()?;
+    /// # let mut i = 0;
+    /// for ka in cert.keys()
+    ///     .with_policy(p, None)
+    ///     .expired()
+    /// {
+    ///     eprintln!("{} has expired", ka.fingerprint());
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 0);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`alive`]: ValidKeyAmalgamationIter::alive()
+    /// [`ValidKeyAmalgamation::alive`]: ValidKeyAmalgamation::alive()
+    pub fn expired(mut self) -> Self
+    {
+        self.expired = Some(());
+        self
+    }
+
     /// Returns keys based on their revocation status.
     ///
     /// A value of `None` disables this filter.
@@ -1350,9 +2303,18 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
             unencrypted_secret: self.unencrypted_secret,
             key_handles: self.key_handles,
             supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
             flags: self.flags,
+            flags_exact: self.flags_exact,
             alive: self.alive,
+            expired: self.expired,
             revoked: self.revoked,
+            pk_algo_filter: self.pk_algo_filter,
+            binding_filter: self.binding_filter,
 
             _p: std::marker::PhantomData,
             _r: std::marker::PhantomData,
@@ -1396,9 +2358,18 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
             unencrypted_secret: Some(true),
             key_handles: self.key_handles,
             supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
             flags: self.flags,
+            flags_exact: self.flags_exact,
             alive: self.alive,
+            expired: self.expired,
             revoked: self.revoked,
+            pk_algo_filter: self.pk_algo_filter,
+            binding_filter: self.binding_filter,
 
             _p: std::marker::PhantomData,
             _r: std::marker::PhantomData,
@@ -1535,6 +2506,98 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
         self
     }
 
+    /// Changes the iterator to only return keys whose public key
+    /// algorithm satisfies `predicate`.
+    ///
+    /// This is useful when the keys are going to be used with a
+    /// device or an implementation that only supports some
+    /// algorithms.  Unlike [`ValidKeyAmalgamationIter::supported`],
+    /// which checks what Sequoia's cryptographic backend supports,
+    /// the caller decides what is acceptable.
+    ///
+    /// This function is cumulative.  If you call this function
+    /// multiple times, then the iterator only returns keys whose
+    /// algorithm satisfies *all* of the predicates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> sequoia_openpgp::Result<()> {
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// # let mut i = 0;
+    /// use openpgp::policy::StandardPolicy;
+    /// use openpgp::types::PublicKeyAlgorithm;
+    ///
+    /// let p = &StandardPolicy::new();
+    ///
+    /// for ka in cert.keys().with_policy(p, None)
+    ///     .supported_by(|a| a == PublicKeyAlgorithm::EdDSA)
+    /// {
+    ///     // Use it.
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 2);
+    /// # Ok(()) }
+    /// ```
+    pub fn supported_by<F>(mut self, predicate: F) -> Self
+        where F: Fn(PublicKeyAlgorithm) -> bool + Send + Sync + 'a
+    {
+        self.pk_algo_filter = Some(match self.pk_algo_filter.take() {
+            Some(filter) => Box::new(move |a| filter(a) && predicate(a)),
+            None => Box::new(predicate),
+        });
+        self
+    }
+
+    /// Changes the iterator to only return keys whose binding
+    /// signature satisfies `predicate`.
+    ///
+    /// The predicate is called with the key's [binding signature]
+    /// under the iterator's policy and reference time.  This can be
+    /// used to select keys based on subpackets that Sequoia doesn't
+    /// interpret itself, e.g., notations.
+    ///
+    /// Like [`ValidKeyAmalgamationIter::supported_by`], this function
+    /// is cumulative: the iterator only returns keys whose binding
+    /// signature satisfies *all* of the predicates.
+    ///
+    /// [binding signature]: super::ValidKeyAmalgamation::binding_signature()
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> sequoia_openpgp::Result<()> {
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// #     let (cert, _) =
+    /// #         CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #         .generate()?;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// let p = &StandardPolicy::new();
+    ///
+    /// let n = cert.keys().with_policy(p, None)
+    ///     .matching_binding(|sig| {
+    ///         sig.notation("usage@example.org").next().is_some()
+    ///     })
+    ///     .count();
+    /// # assert_eq!(n, 0);
+    /// # Ok(()) }
+    /// ```
+    pub fn matching_binding<F>(mut self, predicate: F) -> Self
+        where F: Fn(&Signature) -> bool + Send + Sync + 'a
+    {
+        self.binding_filter = Some(match self.binding_filter.take() {
+            Some(filter) => Box::new(move |s| filter(s) && predicate(s)),
+            None => Box::new(predicate),
+        });
+        self
+    }
+
     /// Changes the iterator to skip the primary key.
     ///
     /// This also changes the iterator's return type.  Instead of
@@ -1558,37 +2621,267 @@ impl<'a, P, R> ValidKeyAmalgamationIter<'a, P, R>
     /// #       .add_storage_encryption_subkey()
     /// #       .add_authentication_subkey()
     /// #       .generate()?;
-    /// #   let mut i = 0;
-    /// for ka in cert.keys().with_policy(p, None).subkeys() {
-    ///     assert!(! ka.primary());
-    /// #   i += 1;
-    /// }
-    /// # assert_eq!(cert.keys().count(), 6);
-    /// # assert_eq!(i, 5);
+    /// #   let mut i = 0;
+    /// for ka in cert.keys().with_policy(p, None).subkeys() {
+    ///     assert!(! ka.primary());
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(cert.keys().count(), 6);
+    /// # assert_eq!(i, 5);
+    /// # Ok(()) }
+    /// ```
+    ///
+    pub fn subkeys(self) -> ValidKeyAmalgamationIter<'a, P, key::SubordinateRole> {
+        ValidKeyAmalgamationIter {
+            cert: self.cert,
+            primary: true,
+            subkey_iter: self.subkey_iter,
+
+            time: self.time,
+            policy: self.policy,
+
+            // The filters.
+            secret: self.secret,
+            unencrypted_secret: self.unencrypted_secret,
+            key_handles: self.key_handles,
+            supported: self.supported,
+            self_revoked: self.self_revoked,
+            backsig: self.backsig,
+            alive_at: self.alive_at,
+            rsa_bits_below: self.rsa_bits_below,
+            curve: self.curve.clone(),
+            flags: self.flags,
+            flags_exact: self.flags_exact,
+            alive: self.alive,
+            expired: self.expired,
+            revoked: self.revoked,
+            pk_algo_filter: self.pk_algo_filter,
+            binding_filter: self.binding_filter,
+
+            _p: std::marker::PhantomData,
+            _r: std::marker::PhantomData,
+        }
+    }
+
+    /// Changes the iterator to skip the primary key.
+    ///
+    /// Unlike [`ValidKeyAmalgamationIter::subkeys`], this doesn't
+    /// change the iterator's return type.  This is useful when the
+    /// keys are processed together with keys from an iterator that
+    /// includes the primary key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .add_transport_encryption_subkey()
+    /// #       .generate()?;
+    /// #   let mut i = 0;
+    /// for ka in cert.keys().with_policy(p, None).subkeys_only() {
+    ///     assert!(! ka.primary());
+    /// #   i += 1;
+    /// }
+    /// # assert_eq!(i, 2);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`ValidKeyAmalgamationIter::subkeys`]: ValidKeyAmalgamationIter::subkeys()
+    pub fn subkeys_only(mut self) -> Self {
+        self.primary = true;
+        self
+    }
+
+    /// Changes the iterator to only consider the primary key.
+    ///
+    /// The iterator returns at most one key: the primary key, if it
+    /// matches the other filters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .add_transport_encryption_subkey()
+    /// #       .generate()?;
+    /// let ka = cert.keys().with_policy(p, None).primary_only()
+    ///     .for_certification()
+    ///     .next()
+    ///     .expect("primary key is certification capable");
+    /// assert!(ka.primary());
+    /// # Ok(()) }
+    /// ```
+    pub fn primary_only(mut self) -> Self {
+        self.subkey_iter = [].iter();
+        self
+    }
+
+    /// Returns the newest key.
+    ///
+    /// This consumes the iterator, and returns the key with the most
+    /// recent creation time among the keys that pass the filters.
+    /// If a subkey and the primary key have the same creation time,
+    /// the subkey is preferred.  Combined with the other filters,
+    /// this implements the usual heuristic for selecting a key, e.g.,
+    /// for signing: use the newest alive, non-revoked signing-capable
+    /// subkey, and fall back to the primary key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .generate()?;
+    /// let ka = cert.keys().with_policy(p, None)
+    ///     .for_signing().alive().revoked(false)
+    ///     .best()
+    ///     .expect("have a signing-capable key");
+    /// # assert!(! ka.primary());
+    /// # Ok(()) }
+    /// ```
+    pub fn best<R2>(self) -> Option<ValidKeyAmalgamation<'a, P, R, R2>>
+        where Self: Iterator<Item=ValidKeyAmalgamation<'a, P, R, R2>>,
+              R2: Copy,
+    {
+        // Orders keys by creation time, and then subkeys before the
+        // primary key.  On ties, the first key wins.
+        let primary = self.cert.map(|cert| cert.fingerprint());
+        self.enumerate()
+            .map(|(i, ka)| {
+                let subkey = Some(ka.key().fingerprint()) != primary;
+                ((ka.key().creation_time(), subkey, Reverse(i)), ka)
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, ka)| ka)
+    }
+
+    /// Returns at most the first `n` keys that pass the filters.
+    ///
+    /// This consumes the iterator, but stops evaluating the filters,
+    /// including the policy checks, as soon as `n` keys have been
+    /// found.  The keys are returned in the iterator's order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .add_signing_subkey()
+    /// #       .generate()?;
+    /// let keys = cert.keys().with_policy(p, None)
+    ///     .for_signing().subkeys()
+    ///     .take_matching(1);
+    /// assert_eq!(keys.len(), 1);
+    /// # Ok(()) }
+    /// ```
+    pub fn take_matching<R2>(self, n: usize)
+        -> Vec<ValidKeyAmalgamation<'a, P, R, R2>>
+        where Self: Iterator<Item=ValidKeyAmalgamation<'a, P, R, R2>>,
+              R2: Copy,
+    {
+        self.take(n).collect()
+    }
+
+    /// Returns the keys that pass the filters, without duplicates.
+    ///
+    /// The keys are returned in the iterator's order.  If the same
+    /// key material is bound more than once, only the first instance
+    /// is returned.  Keys are compared by their fingerprint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_signing_subkey()
+    /// #       .add_transport_encryption_subkey()
+    /// #       .generate()?;
+    /// let keys = cert.keys().with_policy(p, None).for_signing()
+    ///     .collect_keys();
+    /// # assert_eq!(keys.len(), 1);
+    /// for key in keys {
+    ///     println!("Can sign using {}", key.fingerprint());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn collect_keys<R2>(self) -> Vec<Key<P, key::UnspecifiedRole>>
+        where Self: Iterator<Item=ValidKeyAmalgamation<'a, P, R, R2>>,
+              P: 'a + Clone,
+              R: 'a + Clone,
+              R2: Copy,
+    {
+        let mut seen = HashSet::new();
+        self.filter(|ka| seen.insert(ka.fingerprint()))
+            .map(|ka| ka.key().clone().role_into_unspecified())
+            .collect()
+    }
+
+    /// Returns the first key that passes the filters.
+    ///
+    /// This is equivalent to calling `next()`, but consumes the
+    /// iterator.  Note that the keys are returned in the iterator's
+    /// order, which is not necessarily the most suitable key; see
+    /// [`ValidKeyAmalgamationIter::best`] for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    ///
+    /// #   let (cert, _) = CertBuilder::new()
+    /// #       .add_transport_encryption_subkey()
+    /// #       .generate()?;
+    /// let ka = cert.keys().with_policy(p, None)
+    ///     .for_transport_encryption()
+    ///     .first()
+    ///     .expect("have an encryption-capable key");
+    /// # assert!(ka.for_transport_encryption());
     /// # Ok(()) }
     /// ```
-    ///
-    pub fn subkeys(self) -> ValidKeyAmalgamationIter<'a, P, key::SubordinateRole> {
-        ValidKeyAmalgamationIter {
-            cert: self.cert,
-            primary: true,
-            subkey_iter: self.subkey_iter,
-
-            time: self.time,
-            policy: self.policy,
-
-            // The filters.
-            secret: self.secret,
-            unencrypted_secret: self.unencrypted_secret,
-            key_handles: self.key_handles,
-            supported: self.supported,
-            flags: self.flags,
-            alive: self.alive,
-            revoked: self.revoked,
-
-            _p: std::marker::PhantomData,
-            _r: std::marker::PhantomData,
-        }
+    pub fn first<R2>(mut self) -> Option<ValidKeyAmalgamation<'a, P, R, R2>>
+        where Self: Iterator<Item=ValidKeyAmalgamation<'a, P, R, R2>>,
+              R2: Copy,
+    {
+        self.next()
     }
 }
 
@@ -1599,7 +2892,46 @@ mod test {
         parse::Parse,
         cert::builder::CertBuilder,
     };
+    use crate::packet::signature::SignatureBuilder;
     use crate::policy::StandardPolicy as P;
+    use crate::types::SignatureType;
+
+    /// Adds `subkey` to `cert` with the given key flags.
+    ///
+    /// If the subkey is signing capable, the binding signature
+    /// includes a backsig.  If `time` is given, the signatures are
+    /// created at that time.
+    fn cert_with_subkey(cert: Cert, subkey: key::SecretSubkey,
+                        flags: KeyFlags, time: Option<SystemTime>)
+                        -> Cert
+    {
+        let mut primary = cert.primary_key().key().clone()
+            .parts_into_secret().unwrap().into_keypair().unwrap();
+        let builder = |typ| {
+            let b = SignatureBuilder::new(typ);
+            if let Some(t) = time {
+                b.set_signature_creation_time(t).unwrap()
+            } else {
+                b
+            }
+        };
+
+        let mut binding = builder(SignatureType::SubkeyBinding)
+            .set_key_flags(flags.clone()).unwrap();
+        if flags.for_signing() {
+            let mut subkey_signer = subkey.clone().into_keypair().unwrap();
+            let backsig = builder(SignatureType::PrimaryKeyBinding)
+                .sign_primary_key_binding(&mut subkey_signer,
+                                          cert.primary_key().key(), &subkey)
+                .unwrap();
+            binding = binding.set_embedded_signature(backsig).unwrap();
+        }
+        let binding = subkey.bind(&mut primary, &cert, binding).unwrap();
+
+        cert.insert_packets(vec![
+            crate::Packet::from(subkey), binding.into(),
+        ]).unwrap()
+    }
 
     #[test]
     fn key_iter_test() {
@@ -1608,6 +2940,56 @@ mod test {
                    key.keys().count());
     }
 
+    #[test]
+    fn key_iter_order_and_count() {
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .add_authentication_subkey()
+            .generate().unwrap();
+
+        let keys = cert.keys().map(|ka| ka.fingerprint()).collect::<Vec<_>>();
+        assert_eq!(keys.len(), cert.keys().count());
+        assert_eq!(keys[0], cert.fingerprint());
+        assert_eq!(&keys[1..],
+                   &cert.subkeys().map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>()[..]);
+
+        // Filters are respected.
+        assert_eq!(cert.keys().subkeys().count(), 3);
+        assert_eq!(cert.keys().key_handle(cert.fingerprint()).count(), 1);
+        assert_eq!(cert.keys().key_handle(keys[2].clone())
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ keys[2].clone() ]);
+    }
+
+    #[test]
+    fn keys_cloned() {
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        let fps = cert.keys().keys_cloned()
+            .map(|k| k.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(fps.len(), 3);
+        assert_eq!(fps[0], cert.fingerprint());
+        assert_eq!(&fps[1..],
+                   &cert.subkeys().map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>()[..]);
+
+        // The filters are respected, and the parts are preserved.
+        let subkeys = cert.keys().subkeys().secret().keys_cloned()
+            .collect::<Vec<Key<key::SecretParts, key::UnspecifiedRole>>>();
+        assert_eq!(subkeys.len(), 2);
+        assert_eq!(subkeys.iter().map(|k| k.fingerprint())
+                   .collect::<Vec<_>>(), &fps[1..]);
+
+        let cert = cert.strip_secret_key_material();
+        assert_eq!(cert.keys().secret().keys_cloned().count(), 0);
+    }
+
     #[test]
     fn select_no_keys() {
         let p = &P::new();
@@ -1667,6 +3049,541 @@ mod test {
                    2);
     }
 
+    #[test]
+    fn fingerprint_map() {
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .add_authentication_subkey()
+            .generate().unwrap();
+
+        let map = cert.keys().into_fingerprint_map();
+        assert_eq!(map.len(), 4);
+        for ka in cert.keys() {
+            assert_eq!(map.get(&ka.fingerprint()), Some(ka.key()));
+        }
+
+        let map = cert.keys().subkeys().into_fingerprint_map();
+        assert_eq!(map.len(), 3);
+        assert!(! map.contains_key(&cert.fingerprint()));
+
+        // Secret keys keep their secrets.
+        let map = cert.keys().secret().into_fingerprint_map();
+        assert_eq!(map.len(), 4);
+        assert!(map.values().all(|k| k.has_secret()));
+    }
+
+    #[test]
+    fn usable_secret() {
+        use crate::Packet;
+
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .generate().unwrap();
+        let subkey = cert.keys().subkeys().next().unwrap().fingerprint();
+
+        // Encrypt the primary key's secret key material.
+        let cert = Cert::from_packets(cert.into_packets().map(|p| match p {
+            Packet::SecretKey(k) =>
+                k.encrypt_secret(&"streng geheim".into()).unwrap().into(),
+            p => p,
+        })).unwrap();
+
+        assert_eq!(cert.keys().secret().count(), 2);
+        assert_eq!(cert.keys().unencrypted_secret().count(), 1);
+        let usable = cert.keys().usable_secret()
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(usable, vec![ subkey ]);
+    }
+
+    #[test]
+    fn supported_by() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .set_cipher_suite(CipherSuite::RSA2k)
+            .add_signing_subkey()
+            .generate().unwrap();
+
+        // Add an EdDSA signing subkey.
+        let subkey: key::SecretSubkey =
+            key::Key4::generate_ecc(true, Curve::Ed25519).unwrap().into();
+        let fp = subkey.fingerprint();
+        let cert = cert_with_subkey(cert, subkey, KeyFlags::signing(), None);
+        assert_eq!(cert.keys().with_policy(p, None).count(), 3);
+
+        let eddsa = cert.keys().with_policy(p, None)
+            .supported_by(|a| a == PublicKeyAlgorithm::EdDSA)
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(eddsa, vec![ fp ]);
+
+        let rsa = cert.keys().with_policy(p, None)
+            .supported_by(|a| a == PublicKeyAlgorithm::RSAEncryptSign)
+            .count();
+        assert_eq!(rsa, 2);
+
+        // Predicates are cumulative, and compose with other filters.
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .supported_by(|a| a == PublicKeyAlgorithm::EdDSA)
+                   .supported_by(|a| a == PublicKeyAlgorithm::RSAEncryptSign)
+                   .count(), 0);
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .for_certification()
+                   .supported_by(|a| a == PublicKeyAlgorithm::RSAEncryptSign)
+                   .count(), 1);
+    }
+
+    #[test]
+    fn select_rsa_bits_below() {
+        let (cert, _) = CertBuilder::new()
+            .set_cipher_suite(CipherSuite::RSA3k)
+            .generate().unwrap();
+
+        // Add a weak RSA encryption subkey.
+        let subkey: key::SecretSubkey =
+            key::Key4::generate_rsa(1024).unwrap().into();
+        let weak = subkey.fingerprint();
+        let cert = cert_with_subkey(cert, subkey,
+                                    KeyFlags::transport_encryption(), None);
+        assert_eq!(cert.keys().count(), 2);
+
+        assert_eq!(cert.keys().rsa_bits_below(2048)
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ weak.clone() ]);
+        assert_eq!(cert.keys().rsa_bits_below(1024).count(), 0);
+        assert_eq!(cert.keys().rsa_bits_below(4096).count(), 2);
+
+        // The filter is preserved by with_policy.
+        let p = &crate::policy::NullPolicy::new();
+        assert_eq!(cert.keys().rsa_bits_below(2048).with_policy(p, None)
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ weak ]);
+    }
+
+    #[test]
+    fn select_curve() {
+        let (cert, _) = CertBuilder::new()
+            .set_cipher_suite(CipherSuite::Cv25519)
+            .generate().unwrap();
+
+        // Add a NIST P-256 signing subkey.
+        let subkey: key::SecretSubkey =
+            key::Key4::generate_ecc(true, Curve::NistP256).unwrap().into();
+        let p256 = subkey.fingerprint();
+        let cert = cert_with_subkey(cert, subkey, KeyFlags::signing(), None);
+        assert_eq!(cert.keys().count(), 2);
+
+        assert_eq!(cert.keys().with_curve(Curve::NistP256)
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ p256.clone() ]);
+        assert_eq!(cert.keys().with_curve(Curve::Ed25519)
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ cert.fingerprint() ]);
+        assert_eq!(cert.keys().with_curve(Curve::NistP384).count(), 0);
+
+        // The filter is preserved by with_policy.
+        let p = &crate::policy::NullPolicy::new();
+        assert_eq!(cert.keys().with_curve(Curve::NistP256)
+                   .with_policy(p, None)
+                   .map(|ka| ka.fingerprint()).collect::<Vec<_>>(),
+                   vec![ p256 ]);
+    }
+
+    #[test]
+    fn fingerprints() {
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .add_storage_encryption_subkey()
+            .generate().unwrap();
+
+        assert_eq!(cert.keys().fingerprints().collect::<Vec<_>>(),
+                   cert.keys().map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>());
+        assert_eq!(cert.keys().subkeys().fingerprints().collect::<Vec<_>>(),
+                   cert.keys().subkeys().map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>());
+        assert_eq!(cert.keys().fingerprints().count(), 4);
+
+        // Peeking doesn't advance the iterator.
+        let mut fprs = cert.keys().subkeys().fingerprints().peekable();
+        let first = fprs.peek().cloned();
+        assert_eq!(first, fprs.next());
+        assert_eq!(fprs.count(), 2);
+    }
+
+    #[test]
+    fn matching_binding() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .matching_binding(|sig| sig.policy_uri().is_some())
+                   .count(), 0);
+
+        // Update the encryption subkey's binding signature to include
+        // a policy URI.
+        let mut primary = cert.primary_key().key().clone()
+            .parts_into_secret().unwrap().into_keypair().unwrap();
+        let ka = cert.keys().with_policy(p, None)
+            .for_transport_encryption().first().unwrap();
+        let fp = ka.fingerprint();
+        let binding = SignatureBuilder::from(
+            ka.binding_signature().clone())
+            .set_policy_uri("https://example.org/policy").unwrap()
+            .sign_subkey_binding(&mut primary, None,
+                                 ka.key().role_as_subordinate()).unwrap();
+        let cert = cert.insert_packets(binding).unwrap();
+
+        let matching = cert.keys().with_policy(p, None)
+            .matching_binding(|sig| sig.policy_uri().is_some())
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(matching, vec![ fp ]);
+
+        // Predicates are cumulative.
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .matching_binding(|sig| sig.policy_uri().is_some())
+                   .matching_binding(|sig| sig.policy_uri().is_none())
+                   .count(), 0);
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .matching_binding(|sig| sig.policy_uri().is_none())
+                   .count(), 2);
+    }
+
+    #[test]
+    fn select_expired() {
+        use std::time::Duration;
+
+        let p = &P::new();
+        let t0 = crate::now() - Duration::new(7 * 24 * 3600, 0);
+        let (cert, _) = CertBuilder::new()
+            .set_creation_time(t0)
+            .add_subkey(KeyFlags::empty().set_transport_encryption(),
+                        Duration::new(3600, 0), None)
+            .add_subkey(KeyFlags::empty().set_signing(), None, None)
+            .generate().unwrap();
+
+        let expiring = cert.keys().subkeys()
+            .find(|ka| ka.binding_signature(p, t0).unwrap()
+                  .key_validity_period().is_some())
+            .unwrap().fingerprint();
+
+        // Nothing has expired at creation time.
+        assert_eq!(cert.keys().with_policy(p, t0).expired().count(), 0);
+
+        // Afterwards, only the subkey with the expiration time has.
+        let expired = cert.keys().with_policy(p, None).expired()
+            .map(|ka| ka.fingerprint()).collect::<Vec<_>>();
+        assert_eq!(expired, vec![ expiring ]);
+        assert_eq!(cert.keys().with_policy(p, None).alive().count(), 2);
+        assert_eq!(cert.keys().with_policy(p, None).alive().expired().count(),
+                   0);
+    }
+
+    #[test]
+    fn select_alive_at() {
+        use std::time::Duration;
+
+        let t0 = crate::now() - Duration::new(7 * 24 * 3600, 0);
+        let (cert, _) = CertBuilder::new()
+            .set_creation_time(t0)
+            .add_subkey(KeyFlags::empty().set_transport_encryption(),
+                        Duration::new(3600, 0), None)
+            .add_subkey(KeyFlags::empty().set_signing(), None, None)
+            .generate().unwrap();
+
+        // Before the certificate was created, nothing is alive.
+        assert_eq!(cert.keys().alive_at(t0 - Duration::new(1, 0)).count(),
+                   0);
+
+        // At creation time, everything is.
+        assert_eq!(cert.keys().alive_at(t0).count(), 3);
+
+        // Two hours later, the encryption subkey has expired.
+        let alive = cert.keys().alive_at(t0 + Duration::new(2 * 3600, 0))
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(alive.len(), 2);
+        assert!(alive.contains(&cert.fingerprint()));
+        for ka in cert.keys().subkeys() {
+            let expires = ka.self_signatures().next().unwrap()
+                .key_validity_period().is_some();
+            assert_eq!(alive.contains(&ka.fingerprint()), ! expires);
+        }
+
+        // The filter is preserved by with_policy.
+        let p = &P::new();
+        assert_eq!(cert.keys().alive_at(t0 + Duration::new(2 * 3600, 0))
+                   .with_policy(p, t0).count(), 2);
+    }
+
+    #[test]
+    fn select_primary_only_and_subkeys_only() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .add_storage_encryption_subkey()
+            .generate().unwrap();
+
+        let primary = cert.keys().with_policy(p, None).primary_only()
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(primary, vec![ cert.fingerprint() ]);
+
+        // Filters still apply.
+        assert_eq!(cert.keys().with_policy(p, None).primary_only()
+                   .for_signing().count(), 0);
+
+        let subkeys = cert.keys().with_policy(p, None).subkeys_only()
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(subkeys,
+                   cert.keys().subkeys().map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>());
+        assert_eq!(subkeys.len(), 3);
+        assert_eq!(cert.keys().with_policy(p, None).subkeys_only()
+                   .for_signing().count(), 1);
+    }
+
+    #[test]
+    fn capable_now() {
+        use std::time::Duration;
+
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .set_creation_time(crate::now() - Duration::new(7200, 0))
+            .add_signing_subkey()
+            .add_certification_subkey()
+            .add_transport_encryption_subkey()
+            .add_storage_encryption_subkey()
+            .add_subkey(KeyFlags::empty().set_signing(),
+                        Duration::new(3600, 0), None)
+            .generate().unwrap();
+
+        fn fps<'a, I>(i: I) -> Vec<Fingerprint>
+            where I: Iterator<Item=ValidErasedKeyAmalgamation<'a,
+                                                             key::PublicParts>>
+        {
+            i.map(|ka| ka.fingerprint()).collect()
+        }
+
+        let c = fps(cert.keys().certification_capable_now(p));
+        assert_eq!(c.len(), 2);
+        assert_eq!(c, fps(cert.keys().with_policy(p, None)
+                          .for_certification().alive().revoked(false)));
+
+        // The expired signing subkey is not returned.
+        let s = fps(cert.keys().signing_capable_now(p));
+        assert_eq!(s.len(), 1);
+        assert_eq!(s, fps(cert.keys().with_policy(p, None)
+                          .for_signing().alive().revoked(false)));
+
+        let e = fps(cert.keys().encryption_capable_now(p));
+        assert_eq!(e.len(), 2);
+        assert_eq!(e, fps(cert.keys().with_policy(p, None)
+                          .for_transport_encryption()
+                          .for_storage_encryption()
+                          .alive().revoked(false)));
+    }
+
+    #[test]
+    fn best() {
+        use std::time::Duration;
+
+        let p = &P::new();
+        let t0 = crate::now() - Duration::new(3600, 0);
+        let (cert, _) = CertBuilder::new()
+            .set_creation_time(t0)
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        // The primary key and the subkeys have the same creation
+        // time.  The subkey is preferred.
+        let old = cert.keys().with_policy(p, None).for_signing().best()
+            .unwrap();
+        assert!(! old.primary());
+        let old = old.fingerprint();
+        assert_eq!(cert.keys().with_policy(p, None).for_certification()
+                   .best().unwrap().fingerprint(), cert.fingerprint());
+
+        // Add a newer signing subkey.
+        let t1 = t0 + Duration::new(60, 0);
+        let mut new: key::SecretSubkey =
+            key::Key4::generate_ecc(true, Curve::Ed25519).unwrap().into();
+        new.set_creation_time(t1).unwrap();
+        let cert = cert_with_subkey(cert, new.clone(), KeyFlags::signing(),
+                                    Some(t1));
+        assert_eq!(cert.keys().with_policy(p, None).for_signing().count(), 2);
+
+        let best = cert.keys().with_policy(p, None).for_signing().best()
+            .unwrap();
+        assert_eq!(best.fingerprint(), new.fingerprint());
+
+        // The result respects the other filters.
+        assert_eq!(cert.keys().with_policy(p, t0).for_signing().best()
+                   .unwrap().fingerprint(), old);
+        assert!(cert.keys().with_policy(p, None).for_authentication().best()
+                .is_none());
+
+        // Among equally suitable subkeys, the first one wins.
+        let (cert, _) = CertBuilder::new()
+            .set_creation_time(t0)
+            .add_signing_subkey()
+            .add_signing_subkey()
+            .generate().unwrap();
+        let subkeys = cert.keys().with_policy(p, None).for_signing()
+            .subkeys().map(|ka| ka.fingerprint()).collect::<Vec<_>>();
+        assert_eq!(subkeys.len(), 2);
+        assert_eq!(cert.keys().with_policy(p, None).for_signing().best()
+                   .unwrap().fingerprint(), subkeys[0]);
+    }
+
+    #[test]
+    fn select_valid_backsig() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        let signing = cert.keys().subkeys()
+            .find(|ka| ka.binding_signature(p, None).unwrap()
+                  .key_flags().unwrap().for_signing())
+            .unwrap().fingerprint();
+
+        let backsigs = cert.keys().with_valid_backsig()
+            .map(|ka| ka.fingerprint())
+            .collect::<Vec<_>>();
+        assert_eq!(backsigs, vec![ signing.clone() ]);
+        assert_eq!(cert.keys().subkeys().with_valid_backsig().count(), 1);
+        assert_eq!(cert.keys().with_valid_backsig().with_policy(p, None)
+                   .map(|ka| ka.fingerprint())
+                   .collect::<Vec<_>>(),
+                   vec![ signing.clone() ]);
+
+        // Re-sign the signing subkey's binding with a backsig made by
+        // the wrong key.
+        use crate::Packet;
+        use crate::packet::key::Key4;
+        use crate::types::Curve;
+        let rebind = |cert: Cert, flags: KeyFlags| -> Cert {
+            let mut primary = cert.primary_key().key().clone()
+                .parts_into_secret().unwrap().into_keypair().unwrap();
+            let subkey = cert.keys().subkeys().key_handle(signing.clone())
+                .next().unwrap().key().clone();
+            let wrong: key::SecretSubkey =
+                Key4::generate_ecc(true, Curve::Ed25519).unwrap().into();
+            let mut wrong = wrong.into_keypair().unwrap();
+            let backsig =
+                SignatureBuilder::new(SignatureType::PrimaryKeyBinding)
+                .sign_primary_key_binding(&mut wrong,
+                                          cert.primary_key().key(), &subkey)
+                .unwrap();
+            let binding = SignatureBuilder::new(SignatureType::SubkeyBinding)
+                .set_key_flags(flags).unwrap()
+                .set_embedded_signature(backsig).unwrap()
+                .sign_subkey_binding(&mut primary, None, &subkey)
+                .unwrap();
+
+            Cert::from_packets(cert.into_packets().map(|p| match p {
+                Packet::Signature(sig)
+                    if sig.typ() == SignatureType::SubkeyBinding
+                    && sig.embedded_signatures().next().is_some() =>
+                    binding.clone().into(),
+                p => p,
+            })).unwrap()
+        };
+
+        // The binding requires a valid backsig, so it is rejected,
+        // but the subkey is still there.
+        let c = rebind(cert.clone(), KeyFlags::empty().set_signing());
+        assert_eq!(c.keys().subkeys().count(), 2);
+        assert_eq!(c.keys().with_valid_backsig().count(), 0);
+
+        // The binding doesn't require a backsig, so it is valid, but
+        // the backsig is not.
+        let c = rebind(cert, KeyFlags::empty().set_authentication());
+        assert_eq!(c.keys().subkeys().count(), 2);
+        assert_eq!(c.keys().with_policy(p, None)
+                   .key_handle(signing.clone()).count(), 1);
+        assert_eq!(c.keys().with_valid_backsig().count(), 0);
+    }
+
+    #[test]
+    fn select_revoked() {
+        use crate::cert::SubkeyRevocationBuilder;
+        use crate::types::ReasonForRevocation;
+
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+        assert_eq!(cert.keys().revoked().count(), 0);
+
+        let subkey = cert.subkeys().nth(1).unwrap();
+        let fp = subkey.fingerprint();
+        let mut keypair = cert.primary_key().key().clone().parts_into_secret()
+            .unwrap().into_keypair().unwrap();
+        let sig = SubkeyRevocationBuilder::new()
+            .set_reason_for_revocation(
+                ReasonForRevocation::KeyRetired, b"").unwrap()
+            .build(&mut keypair, &cert, subkey.key(), None)
+            .unwrap();
+        let cert = cert.insert_packets(sig).unwrap();
+
+        let revoked = cert.keys().revoked()
+            .map(|ka| ka.fingerprint()).collect::<Vec<_>>();
+        assert_eq!(revoked, vec![ fp.clone() ]);
+        assert_eq!(cert.keys().subkeys().revoked().count(), 1);
+
+        // The filter is independent of the reference time.
+        let t = cert.primary_key().creation_time();
+        assert_eq!(cert.keys().with_policy(p, t).revoked(false).count(), 3);
+        assert_eq!(cert.keys().revoked().with_policy(p, t).count(), 1);
+    }
+
+    #[test]
+    fn select_exact_flags() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .set_primary_key_flags(KeyFlags::empty().set_certification())
+            .add_signing_subkey()
+            .add_subkey(KeyFlags::empty().set_signing().set_certification(),
+                        None, None)
+            .generate().unwrap();
+        let sc = KeyFlags::empty().set_signing().set_certification();
+
+        // key_flags returns keys with any of the flags.
+        assert_eq!(cert.keys().with_policy(p, None).key_flags(&sc).count(),
+                   3);
+
+        // key_flags_exact only returns keys with all of them.
+        let keys = cert.keys().with_policy(p, None).key_flags_exact(&sc)
+            .collect::<Vec<_>>();
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].key_flags().unwrap().normalized_eq(&sc));
+
+        // Signing alone matches both subkeys.
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .key_flags_exact(KeyFlags::empty().set_signing())
+                   .count(),
+                   2);
+
+        // Repeated calls accumulate.
+        assert_eq!(cert.keys().with_policy(p, None)
+                   .key_flags_exact(KeyFlags::empty().set_signing())
+                   .key_flags_exact(KeyFlags::empty().set_certification())
+                   .count(),
+                   1);
+    }
+
     #[test]
     fn selectors() {
         let p = &P::new();
@@ -1770,4 +3687,154 @@ mod test {
         assert_eq!(cert.keys().with_policy(p, None).supported().count(), 1);
         Ok(())
     }
+
+    #[test]
+    fn take_matching() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        let keys = cert.keys().with_policy(p, None).take_matching(1);
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].fingerprint(), cert.fingerprint());
+
+        let keys = cert.keys().with_policy(p, None).for_signing().subkeys()
+            .take_matching(1);
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].for_signing());
+
+        // Asking for more keys than match returns all matches.
+        assert_eq!(cert.keys().with_policy(p, None).for_signing()
+                   .take_matching(10).len(), 2);
+        assert!(cert.keys().with_policy(p, None).take_matching(0).is_empty());
+    }
+
+    #[test]
+    fn collect_keys() {
+        let p = &P::new();
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        let keys = cert.keys().with_policy(p, None).collect_keys();
+        assert_eq!(keys.len(), 4);
+        let fprs = keys.iter().map(|k| k.fingerprint())
+            .collect::<HashSet<_>>();
+        assert_eq!(fprs.len(), keys.len());
+        assert_eq!(keys[0].fingerprint(), cert.fingerprint());
+
+        // Inserting a subkey a second time doesn't duplicate it.
+        let subkey = cert.keys().subkeys().next().unwrap();
+        let packets = vec![
+            crate::Packet::from(subkey.key().clone()),
+            subkey.self_signatures().next().unwrap().clone().into(),
+        ];
+        let cert = cert.insert_packets(packets).unwrap();
+        assert_eq!(cert.keys().with_policy(p, None).collect_keys().len(), 4);
+
+        let keys = cert.keys().with_policy(p, None).for_signing().subkeys()
+            .collect_keys();
+        assert_eq!(keys.len(), 2);
+        assert_ne!(keys[0].fingerprint(), keys[1].fingerprint());
+
+        // Bind the primary key's key material as a subkey.
+        let primary: key::SecretSubkey = cert.primary_key().key().clone()
+            .parts_into_secret().unwrap().role_into_subordinate();
+        let cert = cert_with_subkey(cert, primary, KeyFlags::signing(), None);
+        assert_eq!(cert.keys().subkeys()
+                   .filter(|ka| ka.fingerprint() == cert.fingerprint())
+                   .count(), 1);
+        let all = cert.keys().with_policy(p, None)
+            .map(|ka| ka.fingerprint()).collect::<Vec<_>>();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all.iter().filter(|&fp| fp == &cert.fingerprint()).count(),
+                   2);
+
+        // It is only collected once, as the primary key.
+        let keys = cert.keys().with_policy(p, None).collect_keys();
+        assert_eq!(keys.len(), 4);
+        assert_eq!(keys.iter()
+                   .filter(|k| k.fingerprint() == cert.fingerprint())
+                   .count(), 1);
+        assert_eq!(keys[0].fingerprint(), cert.fingerprint());
+        let keys = cert.keys().with_policy(p, None).for_signing()
+            .collect_keys();
+        assert_eq!(keys.len(), 3);
+    }
+
+    #[test]
+    fn first() {
+        let p = &P::new();
+
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .add_signing_subkey()
+            .generate().unwrap();
+
+        assert_eq!(cert.keys().first().unwrap().fingerprint(),
+                   cert.keys().nth(0).unwrap().fingerprint());
+        assert_eq!(cert.keys().first().unwrap().fingerprint(),
+                   cert.fingerprint());
+        assert_eq!(cert.keys().subkeys().first().unwrap().fingerprint(),
+                   cert.keys().subkeys().nth(0).unwrap().fingerprint());
+
+        assert_eq!(cert.keys().with_policy(p, None).for_signing()
+                   .first().unwrap().fingerprint(),
+                   cert.keys().with_policy(p, None).for_signing()
+                   .nth(0).unwrap().fingerprint());
+        assert_eq!(cert.keys().with_policy(p, None).for_transport_encryption()
+                   .first().unwrap().fingerprint(),
+                   cert.keys().with_policy(p, None).for_transport_encryption()
+                   .nth(0).unwrap().fingerprint());
+
+        // No match.
+        assert!(cert.keys().with_policy(p, None).for_storage_encryption()
+                .first().is_none());
+    }
+
+    #[test]
+    fn any_and_all_secret() {
+        use crate::Packet;
+
+        let (cert, _) = CertBuilder::new()
+            .add_signing_subkey()
+            .add_transport_encryption_subkey()
+            .generate().unwrap();
+
+        // All keys have secret key material.
+        assert!(cert.keys().any_secret());
+        assert!(cert.keys().all_secret());
+        assert!(cert.keys().subkeys().all_secret());
+
+        // None have.
+        let public = cert.clone().strip_secret_key_material();
+        assert!(! public.keys().any_secret());
+        assert!(! public.keys().all_secret());
+        assert!(! public.keys().subkeys().any_secret());
+
+        // Only the primary key has secret key material.
+        let mixed = Cert::from_packets(cert.clone().into_packets().map(|p| {
+            match p {
+                Packet::SecretSubkey(k) =>
+                    Packet::PublicSubkey(k.take_secret().0),
+                p => p,
+            }
+        })).unwrap();
+        assert!(mixed.keys().any_secret());
+        assert!(! mixed.keys().all_secret());
+        assert!(! mixed.keys().subkeys().any_secret());
+        assert_eq!(mixed.keys().any_secret(), mixed.is_tsk());
+
+        // The filters are respected.  No key passes the filter, so
+        // vacuously all of them have secret key material.
+        let fpr = cert.fingerprint();
+        assert!(public.keys().subkeys().key_handle(fpr.clone()).all_secret());
+        assert!(! public.keys().subkeys().key_handle(fpr).any_secret());
+    }
 }
@@ -1390,7 +1390,7 @@ mod tests {
         // Create and sign a revocation certificate.
         let mut signer = cert.primary_key().key().clone()
             .parts_into_secret()?.into_keypair()?;
-        let subkey = cert.keys().subkeys().nth(0).unwrap();
+        let subkey = cert.keys().subkeys().first().unwrap();
         let builder = SignatureBuilder::new(SignatureType::SubkeyRevocation);
         let revocation_builder: SubkeyRevocationBuilder = builder.try_into()?;
         let sig = revocation_builder.build(&mut signer, &cert, subkey.key(), None)?;
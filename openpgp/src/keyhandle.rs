@@ -58,13 +58,15 @@ use crate::{
 /// #    */)?;
 /// if let Packet::Signature(sig) = p {
 ///     let issuers = sig.get_issuers();
-///     assert_eq!(issuers.len(), 2);
+///     // The signature contains both an Issuer Fingerprint and an
+///     // Issuer subpacket, but they refer to the same key.
+///     assert_eq!(issuers.len(), 1);
 ///     assert_eq!(&issuers[0],
 ///                &KeyHandle::Fingerprint(
 ///                    "C03F A641 1B03 AE12 5764  6118 7223 B566 78E0 2528"
 ///                        .parse()?));
-///     assert_eq!(&issuers[1],
-///                &KeyHandle::KeyID("7223 B566 78E0 2528".parse()?));
+///     assert!(issuers[0].aliases(
+///                &KeyHandle::KeyID("7223 B566 78E0 2528".parse()?)));
 /// } else {
 ///     unreachable!("It's a signature!");
 /// }
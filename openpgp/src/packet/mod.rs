@@ -913,6 +913,19 @@ fn packet_path_iter() {
 ///
 ///   [`Signature::normalized_eq`]: Signature::normalized_eq()
 ///
+/// # Size
+///
+/// The size of a signature can be computed without serializing it
+/// using [`MarshalInto::serialized_len`].  For a `Signature`, this
+/// is the length of the packet's body, i.e. the MPIs, both subpacket
+/// areas, and the fixed-size fields.  To include the packet's
+/// header, compute the length of the corresponding [`Packet`]
+/// instead.  This is cheap, and can be used to pre-allocate buffers,
+/// or to reject oversized signatures before they are merged into a
+/// certificate.
+///
+///   [`MarshalInto::serialized_len`]: crate::serialize::MarshalInto::serialized_len()
+///
 /// # Examples
 ///
 /// Add a User ID to an existing certificate:
@@ -55,7 +55,7 @@
 
 use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::{TryInto, TryFrom};
 use std::hash::{Hash, Hasher};
 use std::sync::Mutex;
@@ -327,6 +327,16 @@ pub enum SubpacketTag {
     ///
     ///  [Section 5.2.3.30 of RFC 4880bis]: https://tools.ietf.org/html/draft-ietf-openpgp-rfc4880bis-10.html#section-5.2.3.30
     AttestedCertifications,
+    /// The AEAD ciphersuites that the certificate holder prefers
+    /// (proposed).
+    ///
+    /// A ciphersuite is a pair of a symmetric algorithm and an AEAD
+    /// mode.
+    ///
+    /// See [Section 5.2.3.15 of RFC 9580] for details.
+    ///
+    ///  [Section 5.2.3.15 of RFC 9580]: https://www.rfc-editor.org/rfc/rfc9580.html#section-5.2.3.15
+    PreferredAEADCiphersuites,
     /// Reserved subpacket tag.
     Reserved(u8),
     /// Private subpacket tag.
@@ -373,6 +383,7 @@ impl From<u8> for SubpacketTag {
             34 => SubpacketTag::PreferredAEADAlgorithms,
             35 => SubpacketTag::IntendedRecipient,
             37 => SubpacketTag::AttestedCertifications,
+            39 => SubpacketTag::PreferredAEADCiphersuites,
             0| 1| 8| 13| 14| 15| 17| 18| 19 => SubpacketTag::Reserved(u),
             100..=110 => SubpacketTag::Private(u),
             _ => SubpacketTag::Unknown(u),
@@ -411,6 +422,7 @@ impl From<SubpacketTag> for u8 {
             SubpacketTag::PreferredAEADAlgorithms => 34,
             SubpacketTag::IntendedRecipient => 35,
             SubpacketTag::AttestedCertifications => 37,
+            SubpacketTag::PreferredAEADCiphersuites => 39,
             SubpacketTag::Reserved(u) => u,
             SubpacketTag::Private(u) => u,
             SubpacketTag::Unknown(u) => u,
@@ -428,6 +440,23 @@ impl Arbitrary for SubpacketTag {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::KeyPair;
+    use crate::packet::key::Key4;
+    use crate::parse::Parse;
+    use crate::types::Curve;
+
+    /// Generates an Ed25519 key for signing.
+    fn signing_key<R>() -> Result<Key<key::SecretParts, R>>
+        where R: key::KeyRole
+    {
+        Ok(Key4::generate_ecc(true, Curve::Ed25519)?.into())
+    }
+
+    /// Generates an Ed25519 key pair for signing.
+    fn signing_keypair() -> Result<KeyPair> {
+        signing_key::<key::UnspecifiedRole>()?.into_keypair()
+    }
+
     quickcheck! {
         fn roundtrip(tag: SubpacketTag) -> bool {
             let val: u8 = tag.into();
@@ -448,6 +477,1096 @@ mod tests {
             }
         }
     }
+
+    quickcheck! {
+        fn notation_data_flags_raw_roundtrip(f: NotationDataFlags) -> bool {
+            NotationDataFlags::from_raw(f.to_raw()) == f
+                && f.human_readable() == (f.to_raw() & 0x8000_0000 != 0)
+        }
+    }
+
+    #[test]
+    fn replace_preserving_criticality() -> Result<()> {
+        let mut area = SubpacketArea::default();
+        area.add(Subpacket::new(
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()),
+            true)?)?;
+
+        area.replace_preserving_criticality(
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_certification()))?;
+        assert_eq!(area.iter().count(), 1);
+        let sp = area.subpacket(SubpacketTag::KeyFlags).unwrap();
+        assert!(sp.critical());
+        assert_eq!(sp.value(), &SubpacketValue::KeyFlags(
+            KeyFlags::empty().set_certification()));
+
+        // Non-critical subpackets stay non-critical.
+        area.add(Subpacket::new(SubpacketValue::Revocable(true), false)?)?;
+        area.replace_preserving_criticality(
+            SubpacketValue::Revocable(false))?;
+        let sp = area.subpacket(SubpacketTag::Revocable).unwrap();
+        assert!(! sp.critical());
+        assert_eq!(sp.value(), &SubpacketValue::Revocable(false));
+
+        // New subpackets default to non-critical.
+        area.replace_preserving_criticality(
+            SubpacketValue::PrimaryUserID(true))?;
+        assert!(! area.subpacket(SubpacketTag::PrimaryUserID).unwrap()
+                .critical());
+        assert_eq!(area.iter().count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn merge() -> Result<()> {
+        let notation = |name: &str| Subpacket::new(
+            SubpacketValue::NotationData(
+                NotationData::new(name, b"value", None)), false);
+        let t0 = Timestamp::from(1_600_000_000);
+        let t1 = Timestamp::from(1_700_000_000);
+
+        let mut a = SubpacketArea::default();
+        a.add(notation("a@example.org")?)?;
+        a.add(Subpacket::new(
+            SubpacketValue::SignatureCreationTime(t0), true)?)?;
+
+        let mut b = SubpacketArea::default();
+        b.add(notation("b@example.org")?)?;
+        b.add(Subpacket::new(
+            SubpacketValue::SignatureCreationTime(t1), true)?)?;
+
+        a.merge(&b)?;
+        // Both notations are kept, the creation time is taken from b.
+        assert_eq!(a.iter().count(), 3);
+        assert_eq!(a.iter().filter(|sp| sp.tag() == SubpacketTag::NotationData)
+                   .count(), 2);
+        assert_eq!(a.iter()
+                   .filter(|sp| sp.tag() == SubpacketTag::SignatureCreationTime)
+                   .count(), 1);
+        assert_eq!(a.subpacket(SubpacketTag::SignatureCreationTime)
+                   .unwrap().value(),
+                   &SubpacketValue::SignatureCreationTime(t1));
+
+        // Merging into an empty area copies everything.
+        let mut c = SubpacketArea::default();
+        c.merge(&a)?;
+        assert_eq!(c, a);
+
+        // Overflowing the area fails and leaves it untouched.
+        let big = Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("big@example.org", vec![0; 40000], None)),
+                                 false)?;
+        let mut d = SubpacketArea::default();
+        d.add(big.clone())?;
+        let mut e = SubpacketArea::default();
+        e.add(big)?;
+        let before = d.clone();
+        assert!(d.merge(&e).is_err());
+        assert_eq!(d, before);
+        Ok(())
+    }
+
+    #[test]
+    fn validate() -> Result<()> {
+        use SubpacketValidationContext::*;
+
+        let now = Timestamp::now();
+        let ct = Subpacket::new(
+            SubpacketValue::SignatureCreationTime(now), false)?;
+        let revocable = Subpacket::new(SubpacketValue::Revocable(true), false)?;
+        let notation = Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("a@example.org", b"value", None)), false)?;
+
+        let area = SubpacketArea::new(vec![
+            ct.clone(), revocable.clone(), notation.clone(), notation.clone(),
+        ])?;
+        area.validate(Hashed)?;
+        area.validate(Unhashed)?;
+
+        // The creation time is only required in the hashed area.
+        let area = SubpacketArea::new(vec![ revocable.clone() ])?;
+        assert!(area.validate(Hashed).is_err());
+        area.validate(Unhashed)?;
+
+        // A subpacket whose length field claims more bytes than its
+        // body has.
+        let truncated = Subpacket::with_length(
+            SubpacketLength::from(10), SubpacketValue::Revocable(true), false);
+        let area = SubpacketArea::new(vec![ ct.clone(), truncated ])?;
+        let err = area.validate(Hashed).unwrap_err();
+        assert!(err.to_string().contains("length"), "{}", err);
+
+        // Duplicate creation times.
+        let area = SubpacketArea::new(vec![
+            ct.clone(),
+            Subpacket::new(SubpacketValue::SignatureCreationTime(
+                now.checked_sub(Duration::seconds(1)).unwrap()), false)?,
+        ])?;
+        let err = area.validate(Hashed).unwrap_err();
+        assert!(err.to_string().contains("Duplicate"), "{}", err);
+        assert!(area.validate(Unhashed).is_err());
+
+        // A malformed subpacket.
+        let area = SubpacketArea::new(vec![
+            ct,
+            Subpacket::new(SubpacketValue::Unknown {
+                tag: SubpacketTag::Revocable,
+                body: vec![ 1, 2, 3 ],
+            }, false)?,
+        ])?;
+        let err = area.validate(Hashed).unwrap_err();
+        assert!(err.to_string().contains("malformed"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn into_iter_owned() -> Result<()> {
+        let mut area = SubpacketArea::default();
+        area.add(Subpacket::new(
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()), true)?)?;
+        area.add(Subpacket::new(SubpacketValue::Revocable(false), false)?)?;
+        area.add(Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("a@example.org", b"value", None)), false)?)?;
+
+        // Rebuilding an area from its owned subpackets yields an
+        // equivalent area.
+        let owned: Vec<Subpacket> = area.clone().into_iter().collect();
+        assert_eq!(owned.len(), 3);
+        assert_eq!(SubpacketArea::new(owned)?, area);
+
+        // Owned subpackets can be moved into a filtered copy.
+        let filtered = SubpacketArea::new(
+            area.clone().into_iter()
+                .filter(|sp| sp.tag() != SubpacketTag::Revocable)
+                .collect())?;
+        assert_eq!(filtered.iter().count(), 2);
+        assert!(filtered.subpacket(SubpacketTag::Revocable).is_none());
+        assert_eq!(filtered.subpacket(SubpacketTag::KeyFlags),
+                   area.subpacket(SubpacketTag::KeyFlags));
+        Ok(())
+    }
+
+    #[test]
+    fn from_subpackets() -> Result<()> {
+        let subpackets = vec![
+            Subpacket::new(
+                SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()),
+                true)?,
+            Subpacket::new(SubpacketValue::Revocable(false), false)?,
+            Subpacket::new(SubpacketValue::NotationData(
+                NotationData::new("a@example.org", b"value", None)), false)?,
+        ];
+
+        let area = SubpacketArea::from_subpackets(subpackets.clone())?;
+        assert_eq!(area.iter().count(), 3);
+        for sp in subpackets.iter() {
+            assert_eq!(area.subpacket(sp.tag()), Some(sp));
+        }
+        assert_eq!(area, SubpacketArea::new(subpackets)?);
+
+        // Exceeding the maximum size fails.
+        let big = Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("a@example.org", vec![0; 40000], None)), false)?;
+        assert!(SubpacketArea::from_subpackets(vec![big.clone()]).is_ok());
+        assert!(SubpacketArea::from_subpackets(
+            std::iter::repeat(big).take(2)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn embedded_signature_from_signature() -> Result<()> {
+        use crate::packet::signature::SignatureBuilder;
+        use crate::types::SignatureType;
+
+        let mut pair = signing_keypair()?;
+
+        let embedded = SignatureBuilder::new(SignatureType::Binary)
+            .sign_hash(&mut pair, HashAlgorithm::SHA256.context()?)?;
+        let value = SubpacketValue::from(embedded.clone());
+        assert_eq!(value.tag(), SubpacketTag::EmbeddedSignature);
+        assert_eq!(value, SubpacketValue::EmbeddedSignature(embedded.clone()));
+
+        let sig = SignatureBuilder::new(SignatureType::Binary)
+            .modify_hashed_area(|mut a| {
+                a.add(Subpacket::new(embedded.clone().into(), true)?)?;
+                Ok(a)
+            })?
+            .sign_hash(&mut pair, HashAlgorithm::SHA256.context()?)?;
+
+        // Read it back after a roundtrip.
+        let sig = Signature::from_bytes(&sig.to_vec()?)?;
+        let e = sig.embedded_signatures().collect::<Vec<_>>();
+        assert_eq!(e, vec![&embedded]);
+        Ok(())
+    }
+
+    #[test]
+    fn subpackets_returns_all_instances() -> Result<()> {
+        let mut area = SubpacketArea::default();
+        let flags = [
+            KeyFlags::empty().set_signing(),
+            KeyFlags::empty().set_certification(),
+            KeyFlags::empty().set_authentication(),
+        ];
+        for (i, f) in flags.iter().enumerate() {
+            area.add(Subpacket::new(SubpacketValue::KeyFlags(f.clone()),
+                                    i != 1)?)?;
+            area.add(Subpacket::new(SubpacketValue::Revocable(true), false)?)?;
+        }
+
+        // subpacket returns the last instance...
+        assert_eq!(area.subpacket(SubpacketTag::KeyFlags).unwrap().value(),
+                   &SubpacketValue::KeyFlags(flags[2].clone()));
+        // ... subpackets returns all of them, in order.
+        let all = area.subpackets(SubpacketTag::KeyFlags).collect::<Vec<_>>();
+        assert_eq!(all.len(), 3);
+        for (sp, f) in all.iter().zip(flags.iter()) {
+            assert_eq!(sp.value(), &SubpacketValue::KeyFlags(f.clone()));
+        }
+        assert_eq!(all.iter().filter(|sp| sp.critical()).count(), 2);
+        assert_eq!(area.subpackets(SubpacketTag::Revocable).count(), 3);
+        assert_eq!(area.subpackets(SubpacketTag::Features).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn sort_canonical() -> Result<()> {
+        let notation = |name: &str, value: &[u8]| Subpacket::new(
+            SubpacketValue::NotationData(
+                NotationData::new(name, value, None)), false);
+        let subpackets = vec![
+            notation("b@example.org", b"1")?,
+            Subpacket::new(SubpacketValue::Revocable(false), false)?,
+            notation("a@example.org", b"2")?,
+            Subpacket::new(SubpacketValue::SignatureCreationTime(
+                Timestamp::from(1_600_000_000)), true)?,
+            notation("a@example.org", b"1")?,
+            Subpacket::new(SubpacketValue::Revocable(false), true)?,
+        ];
+
+        let mut area = SubpacketArea::default();
+        for sp in subpackets.iter() {
+            area.add(sp.clone())?;
+        }
+        area.sort_canonical();
+        let sorted = area.clone();
+
+        // The order is by tag, then by serialized form.
+        let tags = area.iter().map(|sp| sp.tag()).collect::<Vec<_>>();
+        assert_eq!(tags, vec![ SubpacketTag::SignatureCreationTime,
+                               SubpacketTag::Revocable,
+                               SubpacketTag::Revocable,
+                               SubpacketTag::NotationData,
+                               SubpacketTag::NotationData,
+                               SubpacketTag::NotationData ]);
+        for w in area.iter().collect::<Vec<_>>().windows(2) {
+            if w[0].tag() == w[1].tag() {
+                assert!(w[0].to_vec()? <= w[1].to_vec()?);
+            }
+        }
+
+        // Sorting is idempotent.
+        area.sort_canonical();
+        assert_eq!(area, sorted);
+
+        // And the result doesn't depend on the insertion order.
+        let mut area = SubpacketArea::default();
+        for sp in subpackets.iter().rev() {
+            area.add(sp.clone())?;
+        }
+        area.sort_canonical();
+        assert_eq!(area, sorted);
+        assert_eq!(area.to_vec()?, sorted.to_vec()?);
+        Ok(())
+    }
+
+    #[test]
+    fn remaining_capacity() -> Result<()> {
+        let mut area = SubpacketArea::default();
+        assert_eq!(area.serialized_len(), 0);
+        assert_eq!(area.remaining_capacity(), SubpacketArea::MAX_SIZE);
+
+        // Fill the area with notations of 1000 value bytes each.
+        let notation = Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("a@example.org", vec![0; 1000], None)),
+                                      false)?;
+        let n = notation.serialized_len();
+        while area.remaining_capacity() >= n {
+            area.add(notation.clone())?;
+        }
+        assert_eq!(area.serialized_len(), area.iter().count() * n);
+        assert_eq!(area.remaining_capacity(),
+                   SubpacketArea::MAX_SIZE - area.serialized_len());
+        assert!(area.remaining_capacity() < n);
+
+        // Adding one more fails, and doesn't change anything.
+        let before = area.remaining_capacity();
+        assert!(area.add(notation).is_err());
+        assert_eq!(area.remaining_capacity(), before);
+
+        // But a small subpacket still fits.
+        let small = Subpacket::new(SubpacketValue::Revocable(false), false)?;
+        assert!(small.serialized_len() <= area.remaining_capacity());
+        area.add(small)?;
+        assert_eq!(area.remaining_capacity(), before - 3);
+        Ok(())
+    }
+
+    #[test]
+    fn trust_signature_with_regular_expression() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let re = "<[^>]+[@.]navy\\.mil>$";
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::GenericCertification)
+            .set_trust_signature(2, 120)?
+            .set_regular_expression(re)?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        assert_eq!(sig.trust_signature(), Some((2, 120)));
+        assert_eq!(sig.regular_expressions().collect::<Vec<_>>(),
+                   vec![ re.as_bytes() ]);
+
+        // The serialized form has exactly one trailing NUL.
+        let sp = sig.hashed_area().subpacket(SubpacketTag::RegularExpression)
+            .unwrap();
+        let raw = sp.to_vec()?;
+        assert!(raw.ends_with(b"navy\\.mil>$\0"));
+        assert!(! raw.ends_with(b"\0\0"));
+
+        // And it survives a round trip.
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        assert_eq!(sig2.trust_signature(), Some((2, 120)));
+        assert_eq!(sig2.regular_expressions().collect::<Vec<_>>(),
+                   vec![ re.as_bytes() ]);
+        Ok(())
+    }
+
+    #[test]
+    fn without_issuer() -> Result<()> {
+        let hash_algo = HashAlgorithm::SHA512;
+        let hash = hash_algo.context()?;
+        let mut keypair = signing_keypair()?;
+
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .without_issuer()
+            .sign_hash(&mut keypair, hash.clone())?;
+        assert!(sig.get_issuers().is_empty());
+        assert_eq!(sig.issuers().count(), 0);
+        assert_eq!(sig.issuer_fingerprints().count(), 0);
+
+        // Existing issuer information is removed, too.
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_issuer(keypair.public().keyid())?
+            .set_issuer_fingerprint(keypair.public().fingerprint())?
+            .without_issuer()
+            .sign_hash(&mut keypair, hash.clone())?;
+        assert!(sig.get_issuers().is_empty());
+
+        // Converting a signature to a builder resets the flag.
+        let t = sig.signature_creation_time().unwrap();
+        let sig = signature::SignatureBuilder::from(sig)
+            .set_signature_creation_time(t + time::Duration::new(1, 0))?
+            .sign_hash(&mut keypair, hash)?;
+        assert_eq!(sig.issuers().count(), 1);
+        assert_eq!(sig.issuer_fingerprints().count(), 1);
+        assert_eq!(sig.get_issuers().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn multiple_regular_expressions() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let com = "<[^>]+[@.]example\\.com>$";
+        let net = "<[^>]+[@.]example\\.net>$";
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::GenericCertification)
+            .set_trust_signature(1, 120)?
+            .set_regular_expression(com)?
+            .add_regular_expression(net)?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        assert_eq!(sig.regular_expressions().collect::<Vec<_>>(),
+                   vec![ com.as_bytes(), net.as_bytes() ]);
+
+        // Both survive a round trip, and the trailing NULs are
+        // stripped from each.
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        assert_eq!(sig2.regular_expressions().collect::<Vec<_>>(),
+                   vec![ com.as_bytes(), net.as_bytes() ]);
+        Ok(())
+    }
+
+    #[test]
+    fn policy_uri_and_preferred_key_server() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let policy = b"https://example.org/policy.txt";
+        let server = b"hkps://keys.example.org";
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::DirectKey)
+            .set_policy_uri(policy)?
+            .set_preferred_key_server(server)?
+            .sign_direct_key(&mut keypair, None)?;
+
+        assert_eq!(sig.policy_uri(), Some(&policy[..]));
+        assert_eq!(sig.preferred_key_server(), Some(&server[..]));
+        assert!(sig.hashed_area().subpacket(SubpacketTag::PolicyURI)
+                .is_some());
+        assert!(sig.hashed_area().subpacket(SubpacketTag::PreferredKeyServer)
+                .is_some());
+
+        // Implausible key servers are rejected.
+        for bad in [&b""[..], b"hkps://keys example.org",
+                    b"hkps://k\xc3\xa9ys.org", b"hkps://keys.example.org\n"]
+            .iter()
+        {
+            assert!(signature::SignatureBuilder::new(
+                crate::types::SignatureType::DirectKey)
+                    .set_preferred_key_server(bad).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn well_known_and_malformed_subpackets() -> Result<()> {
+        let sp = Subpacket::new(
+            SubpacketValue::SignatureCreationTime(Timestamp::from(1)), false)?;
+        assert!(sp.is_well_known());
+        assert!(! sp.is_malformed());
+
+        // A private subpacket isn't understood, but it isn't malformed.
+        let sp = Subpacket::new(SubpacketValue::Unknown {
+            tag: SubpacketTag::Private(100),
+            body: vec![ 1, 2, 3 ],
+        }, false)?;
+        assert!(! sp.is_well_known());
+        assert!(! sp.is_malformed());
+
+        // A standardized tag with an uninterpreted body is malformed.
+        let sp = Subpacket::new(SubpacketValue::Unknown {
+            tag: SubpacketTag::SignatureCreationTime,
+            body: vec![ 1, 2 ],
+        }, false)?;
+        assert!(! sp.is_well_known());
+        assert!(sp.is_malformed());
+
+        // The parser rejects such a subpacket instead of storing it
+        // uninterpreted.
+        let mut pair = signing_keypair()?;
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .sign_hash(&mut pair, HashAlgorithm::SHA256.context()?)?;
+        assert!(Signature::from_bytes(&sig.to_vec()?).is_ok());
+        sig.unhashed_area_mut().add(sp)?;
+        assert!(Signature::from_bytes(&sig.to_vec()?).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn key_revocation_with_reason() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::KeyRevocation)
+            .set_reason_for_revocation(ReasonForRevocation::KeyCompromised,
+                                       b"It was the maid :/")?
+            .sign_direct_key(&mut keypair, None)?;
+
+        let sp = sig.hashed_area()
+            .subpacket(SubpacketTag::ReasonForRevocation).unwrap();
+        assert!(! sp.critical());
+        assert_eq!(sig.reason_for_revocation(),
+                   Some((ReasonForRevocation::KeyCompromised,
+                         &b"It was the maid :/"[..])));
+
+        // The code is serialized as 2.
+        let raw = sp.to_vec()?;
+        assert_eq!(&raw[..3], &[ 1 + 1 + 18, 29, 2 ]);
+
+        // And it survives a round trip.
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        assert_eq!(sig2.reason_for_revocation(),
+                   Some((ReasonForRevocation::KeyCompromised,
+                         &b"It was the maid :/"[..])));
+        Ok(())
+    }
+
+    #[test]
+    fn attested_certifications_roundtrip() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        // Three SHA512 digests, out of order.
+        let digests = vec![ vec![3; 64], vec![1; 64], vec![2; 64] ];
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::AttestationKey)
+            .set_hash_algo(HashAlgorithm::SHA512)
+            .set_attested_certifications(&digests)?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        let sp = sig.hashed_area()
+            .subpacket(SubpacketTag::AttestedCertifications).unwrap();
+        assert_eq!(u8::from(sp.tag()), 37);
+        // Length (2 bytes for 193), tag, 3 * 64 bytes of digests.
+        assert_eq!(sp.to_vec()?.len(), 2 + 1 + 3 * 64);
+
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        // The digests are sorted.
+        assert_eq!(sig2.attested_certifications()?.collect::<Vec<_>>(),
+                   vec![ &[1; 64][..], &[2; 64][..], &[3; 64][..] ]);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_notation() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::DirectKey)
+            .add_notation("one@example.org", b"1", None, false)?
+            .add_notation("two@example.org", b"2", None, false)?
+            .add_notation("three@example.org", b"3", None, false)?
+            .sign_direct_key(&mut keypair, None)?;
+
+        let area = sig.hashed_area_mut();
+        assert_eq!(area.remove_notation("two@example.org"), 1);
+        assert_eq!(area.remove_notation("two@example.org"), 0);
+        assert_eq!(area.remove_notation("no@example.org"), 0);
+
+        let notations = sig.notation_data()
+            .filter(|n| n.name().ends_with("@example.org"))
+            .map(|n| (n.name(), n.value()))
+            .collect::<Vec<_>>();
+        assert_eq!(notations, vec![ ("one@example.org", &b"1"[..]),
+                                    ("three@example.org", &b"3"[..]) ]);
+
+        // Other subpackets are kept.
+        assert!(sig.signature_creation_time().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn unprotected_security_subpackets() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::DirectKey)
+            .set_key_flags(KeyFlags::empty().set_certification())?
+            .sign_direct_key(&mut keypair, None)?;
+
+        // Issuer information in the unhashed area is fine.
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::Issuer(keypair.public().keyid()), false)?)?;
+        assert!(sig.unprotected_security_subpackets().is_empty());
+
+        // Key flags and an expiration time in the unhashed area are not.
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()),
+            false)?)?;
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::KeyExpirationTime(Duration::from(3600)), false)?)?;
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_authentication()),
+            false)?)?;
+        assert_eq!(sig.unprotected_security_subpackets(),
+                   vec![ SubpacketTag::KeyFlags,
+                         SubpacketTag::KeyExpirationTime ]);
+
+        // The injected key flags are ignored.
+        assert_eq!(sig.key_flags(),
+                   Some(KeyFlags::empty().set_certification()));
+        Ok(())
+    }
+
+    #[test]
+    fn subpacket_tags() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::DirectKey)
+            .add_notation("one@example.org", b"1", None, false)?
+            .add_notation("two@example.org", b"2", None, false)?
+            .add_notation("one@example.org", b"3", None, false)?
+            .sign_direct_key(&mut keypair, None)?;
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::NotationData(
+                NotationData::new("four@example.org", b"4", None)), false)?)?;
+
+        let tags = sig.subpacket_tags();
+        // Three notations in the hashed area, one in the unhashed area,
+        // plus the salt.
+        assert_eq!(tags[&SubpacketTag::NotationData], 5);
+        assert_eq!(tags[&SubpacketTag::SignatureCreationTime], 1);
+        assert!(! tags.contains_key(&SubpacketTag::KeyFlags));
+        assert_eq!(tags.values().sum::<usize>(), sig.subpacket_count());
+        assert_eq!(sig.subpacket_count(),
+                   sig.hashed_area().iter().count()
+                   + sig.unhashed_area().iter().count());
+        Ok(())
+    }
+
+    #[test]
+    fn creation_time_in_range() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let t = time::SystemTime::from(Timestamp::from(1_600_000_000));
+        let second = time::Duration::new(1, 0);
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signature_creation_time(t)?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA256.context()?)?;
+
+        // The window is inclusive.
+        assert!(sig.creation_time_in_range(t, t));
+        assert!(sig.creation_time_in_range(t - second, t + second));
+        assert!(sig.creation_time_in_range(t - second, t));
+        assert!(sig.creation_time_in_range(t, t + second));
+        assert!(! sig.creation_time_in_range(t + second, t + 2 * second));
+        assert!(! sig.creation_time_in_range(t - 2 * second, t - second));
+        // An empty window.
+        assert!(! sig.creation_time_in_range(t + second, t - second));
+
+        // A creation time in the unhashed area doesn't count.
+        sig.hashed_area_mut().remove_all(SubpacketTag::SignatureCreationTime);
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::SignatureCreationTime(t.try_into()?), false)?)?;
+        assert!(! sig.creation_time_in_range(t - second, t + second));
+        Ok(())
+    }
+
+    #[test]
+    fn message_signers_user_id() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+        let msg = b"Hello, world!";
+
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signers_user_id("Alice <alice@example.org>")?
+            // Setting it again replaces the previous value.
+            .set_signers_user_id(b"Alice <alice@example.net>")?
+            .sign_message(&mut keypair, msg)?;
+        sig.verify_message(keypair.public(), msg)?;
+
+        assert_eq!(sig.signers_user_id(),
+                   Some(&b"Alice <alice@example.net>"[..]));
+        assert_eq!(sig.hashed_area()
+                   .subpackets(SubpacketTag::SignersUserID).count(), 1);
+        assert!(sig.hashed_area().subpacket(SubpacketTag::SignersUserID)
+                .unwrap().authenticated());
+        Ok(())
+    }
+
+    #[test]
+    fn effective_expiration_time() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let key_creation =
+            time::SystemTime::from(Timestamp::from(1_500_000_000));
+        let t = time::SystemTime::from(Timestamp::from(1_600_000_000));
+        let day = time::Duration::new(24 * 3600, 0);
+        let sign = |keypair: &mut KeyPair,
+                    sig_validity: Option<time::Duration>,
+                    key_validity: Option<time::Duration>|
+            -> Result<signature::Signature>
+        {
+            let mut builder = signature::SignatureBuilder::new(
+                crate::types::SignatureType::DirectKey)
+                .set_signature_creation_time(t)?
+                .set_key_validity_period(key_validity)?;
+            if let Some(v) = sig_validity {
+                builder = builder.set_signature_validity_period(v)?;
+            }
+            builder.sign_hash(keypair, HashAlgorithm::SHA256.context()?)
+        };
+
+        // Neither.
+        let sig = sign(&mut keypair, None, None)?;
+        assert_eq!(sig.effective_expiration_time(key_creation), None);
+
+        // Only the signature expires.
+        let sig = sign(&mut keypair, Some(day), None)?;
+        assert_eq!(sig.effective_expiration_time(key_creation), Some(t + day));
+
+        // Only the key expires.  This is relative to the key's creation
+        // time.
+        let sig = sign(&mut keypair, None, Some(day))?;
+        assert_eq!(sig.effective_expiration_time(key_creation),
+                   Some(key_creation + day));
+
+        // Both, the earlier one wins.
+        let key_validity = t.duration_since(key_creation)? + 2 * day;
+        let sig = sign(&mut keypair, Some(day), Some(key_validity))?;
+        assert_eq!(sig.effective_expiration_time(key_creation), Some(t + day));
+        let sig = sign(&mut keypair, Some(3 * day), Some(key_validity))?;
+        assert_eq!(sig.effective_expiration_time(key_creation),
+                   Some(t + 2 * day));
+        Ok(())
+    }
+
+    #[test]
+    fn embedded_signatures_roundtrip() -> Result<()> {
+        use crate::types::SignatureType;
+
+        let pk: Key<key::SecretParts, key::PrimaryRole> = signing_key()?;
+        let mut pk_signer = pk.clone().into_keypair()?;
+        let subkey: Key<key::SecretParts, key::SubordinateRole>
+            = signing_key()?;
+        let mut sk_signer = subkey.clone().into_keypair()?;
+
+        let backsig = signature::SignatureBuilder::new(
+            SignatureType::PrimaryKeyBinding)
+            .sign_primary_key_binding(&mut sk_signer, &pk, &subkey)?;
+        let sig = signature::SignatureBuilder::new(
+            SignatureType::SubkeyBinding)
+            .set_key_flags(KeyFlags::empty().set_signing())?
+            .set_embedded_signature(backsig.clone())?
+            .sign_subkey_binding(&mut pk_signer, None, &subkey)?;
+
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        // The backsig survives serialization as a signature, not as an
+        // opaque packet.
+        assert_eq!(sig2.embedded_signatures().collect::<Vec<_>>(),
+                   vec![ &backsig ]);
+        sig2.embedded_signatures().next().unwrap().clone()
+            .verify_primary_key_binding(&pk, &subkey)?;
+        Ok(())
+    }
+
+    #[test]
+    fn signature_creation_time_out_of_range() {
+        let too_early = time::UNIX_EPOCH - time::Duration::new(1, 0);
+        let err = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signature_creation_time(too_early).err().unwrap();
+        assert!(matches!(err.downcast_ref::<Error>(),
+                         Some(Error::InvalidArgument(_))));
+        assert!(err.to_string().contains("predates the Unix epoch"));
+
+        // 2106-02-07T06:28:16Z is the first time that doesn't fit.
+        let too_late = time::UNIX_EPOCH
+            + time::Duration::new(std::u32::MAX as u64 + 1, 0);
+        let err = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signature_creation_time(too_late).err().unwrap();
+        assert!(matches!(err.downcast_ref::<Error>(),
+                         Some(Error::InvalidArgument(_))));
+        assert!(err.to_string().contains("exceeds u32 epoch"));
+
+        // The boundaries themselves are fine.
+        let last = time::UNIX_EPOCH
+            + time::Duration::new(std::u32::MAX as u64, 0);
+        for t in [time::UNIX_EPOCH, last] {
+            let b = signature::SignatureBuilder::new(
+                crate::types::SignatureType::Binary)
+                .set_signature_creation_time(t).unwrap();
+            assert_eq!(b.signature_creation_time(), Some(t));
+        }
+    }
+
+    #[test]
+    fn has_subpacket() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        assert!(sig.hashed_area()
+                .contains(SubpacketTag::SignatureCreationTime));
+        assert!(sig.has_subpacket(SubpacketTag::SignatureCreationTime));
+        assert!(! sig.hashed_area().contains(SubpacketTag::Revocable));
+        assert!(! sig.has_subpacket(SubpacketTag::Revocable));
+
+        // Issuer subpackets are looked up in the unhashed area, too.
+        sig.hashed_area_mut().remove_all(SubpacketTag::Issuer);
+        sig.unhashed_area_mut().remove_all(SubpacketTag::Issuer);
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::Issuer(keypair.public().keyid()), false)?)?;
+        assert!(! sig.hashed_area().contains(SubpacketTag::Issuer));
+        assert!(sig.unhashed_area().contains(SubpacketTag::Issuer));
+        assert!(sig.has_subpacket(SubpacketTag::Issuer));
+
+        // But other subpackets are not.
+        sig.unhashed_area_mut().add(Subpacket::new(
+            SubpacketValue::Revocable(false), false)?)?;
+        assert!(sig.unhashed_area().contains(SubpacketTag::Revocable));
+        assert!(! sig.has_subpacket(SubpacketTag::Revocable));
+        Ok(())
+    }
+
+    #[test]
+    fn merge_hashed_subpackets() -> Result<()> {
+        use crate::types::SignatureType;
+
+        let name = "dynamic@example.org";
+        let values = vec![
+            SubpacketValue::Revocable(false),
+            SubpacketValue::KeyFlags(KeyFlags::empty().set_certification()),
+            SubpacketValue::NotationData(NotationData::new(name, b"1", None)),
+        ];
+
+        let builder = signature::SignatureBuilder::new(
+            SignatureType::DirectKey)
+            .set_revocable(true)?
+            .add_notation(name, b"0", None, false)?
+            .merge_hashed_subpackets(values, false)?;
+
+        // Single-valued subpackets are replaced, notations are added.
+        assert_eq!(builder.hashed_area().iter().count(), 4);
+        assert_eq!(builder.revocable(), Some(false));
+        assert_eq!(builder.key_flags(),
+                   Some(KeyFlags::empty().set_certification()));
+        assert_eq!(builder.notation(name).collect::<Vec<_>>(),
+                   vec![ &b"0"[..], &b"1"[..] ]);
+        assert!(builder.hashed_area().iter().all(|sp| ! sp.critical()));
+
+        // Criticality is applied to all values.
+        let builder = signature::SignatureBuilder::new(
+            SignatureType::DirectKey)
+            .merge_hashed_subpackets(
+                vec![ SubpacketValue::Revocable(false) ], true)?;
+        assert!(builder.hashed_area().subpacket(SubpacketTag::Revocable)
+                .unwrap().critical());
+        Ok(())
+    }
+
+    #[test]
+    fn private_subpacket_roundtrip() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        for tag in [0, 99, 111, 255] {
+            assert!(signature::SignatureBuilder::new(
+                crate::types::SignatureType::Binary)
+                    .set_private_subpacket(tag, vec![1], false).is_err());
+        }
+
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_private_subpacket(100, b"first".to_vec(), false)?
+            .set_private_subpacket(110, b"second".to_vec(), true)?
+            .set_private_subpacket(100, b"replaced".to_vec(), false)?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        let sig = Signature::from_bytes(&sig.to_vec()?)?;
+
+        assert_eq!(sig.hashed_area().subpackets(SubpacketTag::Private(100))
+                   .count(), 1);
+        let sp = sig.subpacket(SubpacketTag::Private(100)).unwrap();
+        assert!(! sp.critical());
+        assert_eq!(sp.value(), &SubpacketValue::Unknown {
+            tag: SubpacketTag::Private(100),
+            body: b"replaced".to_vec(),
+        });
+        let sp = sig.subpacket(SubpacketTag::Private(110)).unwrap();
+        assert!(sp.critical());
+        assert_eq!(sp.value(), &SubpacketValue::Unknown {
+            tag: SubpacketTag::Private(110),
+            body: b"second".to_vec(),
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn strict_mode_rejects_empty_values() -> Result<()> {
+        use crate::types::SignatureType;
+
+        // The default is permissive.
+        let sig = signature::SignatureBuilder::new(SignatureType::DirectKey)
+            .set_preferred_symmetric_algorithms(vec![])?
+            .set_policy_uri("")?;
+        assert_eq!(sig.preferred_symmetric_algorithms(), Some(&[][..]));
+        assert_eq!(sig.policy_uri(), Some(&b""[..]));
+
+        let sig = signature::SignatureBuilder::new(SignatureType::DirectKey)
+            .strict();
+        assert!(sig.clone().set_preferred_symmetric_algorithms(vec![])
+                .is_err());
+        assert!(sig.clone().set_preferred_hash_algorithms(vec![]).is_err());
+        assert!(sig.clone().set_preferred_compression_algorithms(vec![])
+                .is_err());
+        assert!(sig.clone().set_preferred_aead_algorithms(vec![]).is_err());
+        assert!(sig.clone().set_policy_uri("").is_err());
+
+        // Non-empty values are still accepted.
+        let sig = sig
+            .set_preferred_symmetric_algorithms(
+                vec![ SymmetricAlgorithm::AES256 ])?
+            .set_policy_uri("https://example.org/policy")?;
+        assert_eq!(sig.preferred_symmetric_algorithms(),
+                   Some(&[SymmetricAlgorithm::AES256][..]));
+        Ok(())
+    }
+
+    #[test]
+    fn trust_depth_and_amount() -> Result<()> {
+        use crate::types::SignatureType;
+
+        let mut signer = signing_keypair()?;
+        let hash = HashAlgorithm::SHA256.context()?;
+
+        for &(depth, amount, level) in [
+            (1, 60, TrustLevel::Partial),
+            (2, 119, TrustLevel::Partial),
+            (1, 120, TrustLevel::Complete),
+            (0, 255, TrustLevel::Complete),
+        ].iter() {
+            let sig = signature::SignatureBuilder::new(
+                SignatureType::GenericCertification)
+                .set_trust_signature(depth, amount)?
+                .sign_hash(&mut signer, hash.clone())?;
+            let sig = Signature::from_bytes(&sig.to_vec()?)?;
+
+            assert_eq!(sig.trust_signature(), Some((depth, amount)));
+            assert_eq!(sig.trust_depth(), Some(depth));
+            assert_eq!(sig.trust_amount(), Some(amount));
+            assert_eq!(TrustLevel::from_amount(sig.trust_amount().unwrap()),
+                       level);
+        }
+
+        let sig = signature::SignatureBuilder::new(
+            SignatureType::GenericCertification)
+            .sign_hash(&mut signer, hash)?;
+        assert_eq!(sig.trust_depth(), None);
+        assert_eq!(sig.trust_amount(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn preferred_aead_ciphersuites_roundtrip() -> Result<()> {
+        use crate::types::SignatureType;
+
+        let mut signer = signing_keypair()?;
+
+        let suites = [
+            (SymmetricAlgorithm::AES256, AEADAlgorithm::OCB),
+            (SymmetricAlgorithm::AES128, AEADAlgorithm::EAX),
+            (SymmetricAlgorithm::Unknown(99), AEADAlgorithm::Unknown(42)),
+        ];
+        let sig = signature::SignatureBuilder::new(SignatureType::DirectKey)
+            .set_preferred_aead_ciphersuites(&suites)?
+            .sign_direct_key(&mut signer, None)?;
+        let sp = sig.hashed_area()
+            .subpacket(SubpacketTag::PreferredAEADCiphersuites).unwrap();
+        assert_eq!(sp.value().serialized_len(), 2 * suites.len());
+        assert_eq!(sp.value().to_vec()?, vec![9, 2, 7, 1, 99, 42]);
+
+        let sig = Signature::from_bytes(&sig.to_vec()?)?;
+        assert_eq!(sig.preferred_aead_ciphersuites(), Some(&suites[..]));
+        assert_eq!(u8::from(SubpacketTag::PreferredAEADCiphersuites), 39);
+        assert_eq!(SubpacketTag::from(39),
+                   SubpacketTag::PreferredAEADCiphersuites);
+        Ok(())
+    }
+
+    #[test]
+    fn preferred_aead_algorithms_roundtrip() -> Result<()> {
+        let mut keypair = signing_keypair()?;
+
+        let pref = vec![AEADAlgorithm::OCB, AEADAlgorithm::EAX,
+                        AEADAlgorithm::Unknown(99)];
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::DirectKey)
+            .set_preferred_aead_algorithms(pref.clone())?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        let sp = sig.hashed_area()
+            .subpacket(SubpacketTag::PreferredAEADAlgorithms).unwrap();
+        assert_eq!(u8::from(sp.tag()), 34);
+        assert_eq!(sp.to_vec()?, vec![4, 34, 2, 1, 99]);
+
+        let sig2 = Signature::from_bytes(&sig.to_vec()?)?;
+        assert_eq!(sig2.preferred_aead_algorithms(), Some(&pref[..]));
+        assert_eq!(sig, sig2);
+        Ok(())
+    }
+
+    #[test]
+    fn primary_userid_and_features() -> Result<()> {
+        use crate::packet::UserID;
+
+        let key: Key<key::SecretParts, key::PrimaryRole> = signing_key()?;
+        let mut keypair = key.clone().into_keypair()?;
+        let userid = UserID::from("Alice <alice@example.org>");
+
+        let mut sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::PositiveCertification)
+            .set_primary_userid(false)?
+            .set_primary_userid(true)?
+            .set_features(Features::empty().set_mdc())?
+            .sign_userid_binding(&mut keypair, key.parts_as_public(),
+                                 &userid)?;
+
+        // Setting the flag twice doesn't duplicate the subpacket.
+        assert_eq!(sig.hashed_area().iter()
+                   .filter(|sp| sp.tag() == SubpacketTag::PrimaryUserID)
+                   .count(), 1);
+        assert_eq!(sig.primary_userid(), Some(true));
+        assert!(sig.features().unwrap().supports_mdc());
+        assert!(! sig.features().unwrap().supports_aead());
+
+        sig.verify_userid_binding(keypair.public(),
+                                  key.parts_as_public(), &userid)?;
+        Ok(())
+    }
+
+    #[test]
+    fn signature_creation_and_expiration_time() -> Result<()> {
+        use std::time::{Duration as StdDuration, UNIX_EPOCH};
+
+        let mut keypair = signing_keypair()?;
+
+        let t = UNIX_EPOCH + StdDuration::new(1_600_000_000, 0);
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signature_creation_time(t)?
+            .set_signature_validity_period(StdDuration::new(3600, 0))?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+
+        // Compare with the raw values.
+        let raw_ct = match sig.subpacket(SubpacketTag::SignatureCreationTime)
+            .unwrap().value()
+        {
+            SubpacketValue::SignatureCreationTime(t) => u32::from(*t),
+            _ => unreachable!(),
+        };
+        let raw_vp = match sig.subpacket(SubpacketTag::SignatureExpirationTime)
+            .unwrap().value()
+        {
+            SubpacketValue::SignatureExpirationTime(d) => u32::from(*d),
+            _ => unreachable!(),
+        };
+        assert_eq!(raw_ct, 1_600_000_000);
+        assert_eq!(raw_vp, 3600);
+        assert_eq!(sig.signature_creation_time(),
+                   Some(UNIX_EPOCH + StdDuration::new(raw_ct as u64, 0)));
+        assert_eq!(sig.signature_expiration_time(),
+                   Some(UNIX_EPOCH
+                        + StdDuration::new(raw_ct as u64 + raw_vp as u64, 0)));
+
+        // Without the subpackets, both are None.
+        let mut sig = sig;
+        sig.hashed_area_mut()
+            .remove_all(SubpacketTag::SignatureExpirationTime);
+        assert_eq!(sig.signature_expiration_time(), None);
+        sig.hashed_area_mut().remove_all(SubpacketTag::SignatureCreationTime);
+        assert_eq!(sig.signature_creation_time(), None);
+        assert_eq!(sig.signature_expiration_time(), None);
+
+        // The largest values don't overflow.
+        let sig = signature::SignatureBuilder::new(
+            crate::types::SignatureType::Binary)
+            .set_signature_creation_time(
+                UNIX_EPOCH + StdDuration::new(u32::MAX as u64, 0))?
+            .set_signature_validity_period(
+                StdDuration::new(u32::MAX as u64, 0))?
+            .sign_hash(&mut keypair, HashAlgorithm::SHA512.context()?)?;
+        assert_eq!(sig.signature_expiration_time(),
+                   (UNIX_EPOCH + StdDuration::new(u32::MAX as u64, 0))
+                   .checked_add(StdDuration::new(u32::MAX as u64, 0)));
+        Ok(())
+    }
 }
 
 /// Subpacket area.
@@ -588,6 +1707,65 @@ impl<'a> IntoIterator for &'a SubpacketArea {
     }
 }
 
+/// Consumes the subpacket area, yielding owned subpackets.
+///
+/// This is useful to build a modified copy of a subpacket area:
+///
+/// ```
+/// # use sequoia_openpgp as openpgp;
+/// # use openpgp::packet::signature::subpacket::{
+/// #     Subpacket, SubpacketArea, SubpacketTag, SubpacketValue,
+/// # };
+/// # fn main() -> openpgp::Result<()> {
+/// # let mut area = SubpacketArea::default();
+/// # area.add(Subpacket::new(SubpacketValue::Revocable(false), false)?)?;
+/// # area.add(Subpacket::new(SubpacketValue::ExportableCertification(false), false)?)?;
+/// let area = SubpacketArea::new(
+///     area.into_iter()
+///         .filter(|sp| sp.tag() != SubpacketTag::Revocable)
+///         .collect())?;
+/// # assert_eq!(area.iter().count(), 1);
+/// # Ok(()) }
+/// ```
+impl IntoIterator for SubpacketArea {
+    type Item = Subpacket;
+    type IntoIter = std::vec::IntoIter<Subpacket>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.packets.into_iter()
+    }
+}
+
+/// Returns whether a subpacket area may meaningfully contain more
+/// than one subpacket with the given tag.
+fn multi_valued(tag: SubpacketTag) -> bool {
+    matches!(tag,
+             SubpacketTag::NotationData
+             | SubpacketTag::RevocationKey
+             | SubpacketTag::Issuer
+             | SubpacketTag::IssuerFingerprint
+             | SubpacketTag::IntendedRecipient
+             | SubpacketTag::RegularExpression
+             | SubpacketTag::EmbeddedSignature)
+}
+
+/// The subpacket area that [`SubpacketArea::validate`] checks.
+///
+/// Note: This enum cannot be exhaustively matched to allow future
+/// extensions.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubpacketValidationContext {
+    /// The hashed subpacket area.
+    ///
+    /// The hashed area must contain a Signature Creation Time
+    /// subpacket.
+    Hashed,
+    /// The unhashed subpacket area.
+    Unhashed,
+}
+assert_send_and_sync!(SubpacketValidationContext);
+
 impl SubpacketArea {
     /// The maximum size of a subpacket area.
     pub const MAX_SIZE: usize = (1 << 16) - 1;
@@ -607,6 +1785,43 @@ impl SubpacketArea {
         }
     }
 
+    /// Returns a new subpacket area containing the given
+    /// `subpackets`.
+    ///
+    /// This is like [`SubpacketArea::new`], but accepts any iterator.
+    /// Returns [`Error::InvalidArgument`] if the subpackets exceed
+    /// the maximum size of a subpacket area.
+    ///
+    ///   [`SubpacketArea::new`]: Self::new()
+    ///   [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::subpacket::{
+    ///     Subpacket,
+    ///     SubpacketArea,
+    ///     SubpacketTag,
+    ///     SubpacketValue,
+    /// };
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let area = SubpacketArea::from_subpackets(
+    ///     [ SubpacketValue::Revocable(false),
+    ///       SubpacketValue::ExportableCertification(false) ]
+    ///         .iter().cloned()
+    ///         .map(|v| Subpacket::new(v, false))
+    ///         .collect::<openpgp::Result<Vec<_>>>()?)?;
+    /// assert!(area.subpacket(SubpacketTag::Revocable).is_some());
+    /// # Ok(()) }
+    /// ```
+    pub fn from_subpackets<I>(subpackets: I) -> Result<SubpacketArea>
+        where I: IntoIterator<Item = Subpacket>,
+    {
+        Self::new(subpackets.into_iter().collect())
+    }
+
     // Initialize `Signature::hashed_area_parsed` from
     // `Signature::hashed_area`, if necessary.
     fn cache_init(&self) {
@@ -625,6 +1840,130 @@ impl SubpacketArea {
         *self.parsed.lock().unwrap().borrow_mut() = None;
     }
 
+    /// Returns the length of the serialized subpacket area.
+    ///
+    /// This is the sum of the lengths of the serialized subpackets,
+    /// and does not include the two-octet length field that precedes
+    /// the area in a signature packet.
+    pub fn serialized_len(&self) -> usize {
+        MarshalInto::serialized_len(self)
+    }
+
+    /// Returns the number of bytes that can still be added.
+    ///
+    /// A subpacket area can be at most [`SubpacketArea::MAX_SIZE`]
+    /// bytes large.  [`SubpacketArea::add`] and
+    /// [`SubpacketArea::replace`] return [`Error::MalformedPacket`]
+    /// if a subpacket would exceed this limit.  This can be used to
+    /// check whether there is enough room for a subpacket before
+    /// adding it.  Note: the space needed by a subpacket includes its
+    /// header, see [`MarshalInto::serialized_len`].
+    ///
+    ///   [`SubpacketArea::add`]: Self::add()
+    ///   [`SubpacketArea::replace`]: Self::replace()
+    ///   [`Error::MalformedPacket`]: crate::Error::MalformedPacket
+    ///   [`MarshalInto::serialized_len`]: crate::serialize::MarshalInto::serialized_len()
+    pub fn remaining_capacity(&self) -> usize {
+        Self::MAX_SIZE.saturating_sub(self.serialized_len())
+    }
+
+    /// Checks that the subpacket area is structurally sound.
+    ///
+    /// When parsing a signature, Sequoia rejects subpackets that are
+    /// truncated or malformed, but tolerates duplicate subpackets.
+    /// This function is stricter.  It returns
+    /// [`Error::MalformedPacket`] if:
+    ///
+    ///   - a subpacket's length field does not match the length of
+    ///     its body,
+    ///   - a subpacket is [malformed](Subpacket::is_malformed()),
+    ///   - the area exceeds [`SubpacketArea::MAX_SIZE`],
+    ///   - a subpacket that is meaningful at most once, like the
+    ///     Signature Creation Time subpacket, occurs more than once,
+    ///     or
+    ///   - `context` is [`SubpacketValidationContext::Hashed`], and
+    ///     there is no Signature Creation Time subpacket.
+    ///
+    /// Subpackets with reserved, private, or unknown tags may occur
+    /// any number of times.
+    ///
+    /// The first two conditions cannot arise in a parsed area, only
+    /// in one built by hand, e.g. using [`Subpacket::new`] with a
+    /// [`SubpacketValue::Unknown`] that uses a well-known tag.
+    ///
+    ///   [`Error::MalformedPacket`]: crate::Error::MalformedPacket
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::packet::signature::subpacket::SubpacketValidationContext;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    /// let (cert, _) = CertBuilder::new().generate()?;
+    /// let sig = cert.with_policy(p, None)?.direct_key_signature()?;
+    ///
+    /// sig.hashed_area().validate(SubpacketValidationContext::Hashed)?;
+    /// sig.unhashed_area().validate(SubpacketValidationContext::Unhashed)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn validate(&self, context: SubpacketValidationContext)
+                    -> Result<()>
+    {
+        for (i, sp) in self.packets.iter().enumerate() {
+            let body_len = 1 /* Tag */ + sp.value().serialized_len();
+            if sp.length.len() != body_len {
+                return Err(Error::MalformedPacket(format!(
+                    "Subpacket {} ({}): length field says {} bytes, \
+                     but the body is {} bytes",
+                    i, sp.tag(), sp.length.len(), body_len)).into());
+            }
+
+            if sp.is_malformed() {
+                return Err(Error::MalformedPacket(format!(
+                    "Subpacket {} ({}) is malformed", i, sp.tag())).into());
+            }
+        }
+
+        if self.serialized_len() > Self::MAX_SIZE {
+            return Err(Error::MalformedPacket(format!(
+                "Subpacket area exceeds maximum size: {}",
+                self.serialized_len())).into());
+        }
+
+        let mut seen = Vec::new();
+        for sp in self.packets.iter() {
+            let tag = sp.tag();
+            if multi_valued(tag) || matches!(
+                tag,
+                SubpacketTag::Reserved(_)
+                    | SubpacketTag::PlaceholderForBackwardCompatibility
+                    | SubpacketTag::Private(_)
+                    | SubpacketTag::Unknown(_))
+            {
+                continue;
+            }
+            if seen.contains(&tag) {
+                return Err(Error::MalformedPacket(format!(
+                    "Duplicate {} subpacket", tag)).into());
+            }
+            seen.push(tag);
+        }
+
+        if context == SubpacketValidationContext::Hashed
+            && ! self.contains(SubpacketTag::SignatureCreationTime)
+        {
+            return Err(Error::MalformedPacket(
+                "Hashed area lacks a Signature Creation Time subpacket"
+                    .into()).into());
+        }
+
+        Ok(())
+    }
+
     /// Iterates over the subpackets.
     ///
     /// # Examples
@@ -847,6 +2186,28 @@ impl SubpacketArea {
         self.iter_mut().filter(move |sp| sp.tag() == target)
     }
 
+    /// Returns whether the subpacket area contains a subpacket with
+    /// the specified tag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::packet::signature::subpacket::{
+    /// #     Subpacket, SubpacketArea, SubpacketTag, SubpacketValue,
+    /// # };
+    /// # fn main() -> openpgp::Result<()> {
+    /// let mut area = SubpacketArea::default();
+    /// assert!(! area.contains(SubpacketTag::Revocable));
+    ///
+    /// area.add(Subpacket::new(SubpacketValue::Revocable(false), false)?)?;
+    /// assert!(area.contains(SubpacketTag::Revocable));
+    /// # Ok(()) }
+    /// ```
+    pub fn contains(&self, tag: SubpacketTag) -> bool {
+        self.subpacket(tag).is_some()
+    }
+
     /// Adds the given subpacket.
     ///
     /// Adds the given subpacket to the subpacket area.  If the
@@ -1022,12 +2383,150 @@ impl SubpacketArea {
         Ok(())
     }
 
+    /// Replaces the value of a subpacket, preserving its critical
+    /// bit.
+    ///
+    /// Like [`SubpacketArea::replace`], this removes all subpackets
+    /// with `value`'s tag, and then adds a new subpacket holding
+    /// `value`.  The new subpacket is marked as critical if the
+    /// subpacket being replaced (i.e., the one returned by
+    /// [`SubpacketArea::subpacket`]) was critical.  If there is no
+    /// such subpacket, the new subpacket is not critical.
+    ///
+    /// [`SubpacketArea::replace`]: Self::replace()
+    /// [`SubpacketArea::subpacket`]: Self::subpacket()
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MalformedPacket` if adding the packet makes
+    /// the subpacket area exceed the size limit.
+    pub fn replace_preserving_criticality(&mut self, value: SubpacketValue)
+                                          -> Result<()>
+    {
+        let critical = self.subpacket(value.tag())
+            .map(|sp| sp.critical())
+            .unwrap_or(false);
+        self.replace(Subpacket::new(value, critical)?)
+    }
+
+    /// Merges the subpackets of `other` into this area.
+    ///
+    /// Subpackets that may reasonably occur multiple times, like
+    /// [`Notation Data`], [`Revocation Key`], or issuer information,
+    /// are appended.  For all other subpackets, `other`'s instances
+    /// take precedence: if `other` contains a subpacket with a given
+    /// tag, all subpackets with that tag are first removed from this
+    /// area.
+    ///
+    ///   [`Notation Data`]: https://tools.ietf.org/html/rfc4880#section-5.2.3.16
+    ///   [`Revocation Key`]: https://tools.ietf.org/html/rfc4880#section-5.2.3.15
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MalformedPacket` if the merged subpacket area
+    /// would exceed the size limit.  In that case, this area is not
+    /// modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::subpacket::{
+    ///     Subpacket,
+    ///     SubpacketArea,
+    ///     SubpacketTag,
+    ///     SubpacketValue,
+    /// };
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let mut a = SubpacketArea::default();
+    /// a.add(Subpacket::new(SubpacketValue::Revocable(true), false)?)?;
+    ///
+    /// let mut b = SubpacketArea::default();
+    /// b.add(Subpacket::new(SubpacketValue::Revocable(false), false)?)?;
+    ///
+    /// a.merge(&b)?;
+    /// assert_eq!(a.iter().count(), 1);
+    /// assert_eq!(a.subpacket(SubpacketTag::Revocable).unwrap().value(),
+    ///            &SubpacketValue::Revocable(false));
+    /// # Ok(()) }
+    /// ```
+    pub fn merge(&mut self, other: &SubpacketArea) -> Result<()> {
+        let overridden: Vec<SubpacketTag> = other.iter()
+            .map(|sp| sp.tag())
+            .filter(|&tag| ! multi_valued(tag))
+            .collect();
+
+        let merged: Vec<Subpacket> = self.packets.iter()
+            .filter(|sp| ! overridden.contains(&sp.tag()))
+            .chain(other.iter())
+            .cloned()
+            .collect();
+
+        if merged.iter().map(|sp| sp.serialized_len()).sum::<usize>()
+            > Self::MAX_SIZE
+        {
+            return Err(Error::MalformedPacket(
+                "Subpacket area exceeds maximum size".into()).into());
+        }
+
+        self.cache_invalidate();
+        self.packets = merged;
+        for sp in self.packets.iter_mut() {
+            sp.set_authenticated(false);
+        }
+        Ok(())
+    }
+
     /// Removes all subpackets with the given tag.
     pub fn remove_all(&mut self, tag: SubpacketTag) {
         self.cache_invalidate();
         self.packets.retain(|sp| sp.tag() != tag);
     }
 
+    /// Removes all Notation Data subpackets with the given name.
+    ///
+    /// Unlike `remove_all(SubpacketTag::NotationData)`, this keeps
+    /// notations with other names.  Returns the number of subpackets
+    /// that were removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::subpacket::{
+    ///     NotationData,
+    ///     NotationDataFlags,
+    ///     Subpacket,
+    ///     SubpacketArea,
+    ///     SubpacketValue,
+    /// };
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let mut area = SubpacketArea::default();
+    /// for name in &["a@example.org", "b@example.org", "a@example.org"] {
+    ///     area.add(Subpacket::new(SubpacketValue::NotationData(
+    ///         NotationData::new(name, b"value", NotationDataFlags::empty())),
+    ///         false)?)?;
+    /// }
+    ///
+    /// assert_eq!(area.remove_notation("a@example.org"), 2);
+    /// assert_eq!(area.iter().count(), 1);
+    /// # Ok(()) }
+    /// ```
+    pub fn remove_notation<N>(&mut self, name: N) -> usize
+        where N: AsRef<str>
+    {
+        let name = name.as_ref();
+        let before = self.packets.len();
+        self.cache_invalidate();
+        self.packets.retain(|sp| match sp.value() {
+            SubpacketValue::NotationData(n) => n.name() != name,
+            _ => true,
+        });
+        before - self.packets.len()
+    }
+
     /// Removes all subpackets.
     pub fn clear(&mut self) {
         self.cache_invalidate();
@@ -1054,6 +2553,70 @@ impl SubpacketArea {
         // slice::sort_by is stable.
         self.packets.sort_by(|a, b| u8::from(a.tag()).cmp(&b.tag().into()));
     }
+
+    /// Sorts the subpackets into a canonical order.
+    ///
+    /// Like [`SubpacketArea::sort`], this orders the subpackets by
+    /// the numeric value of their tag.  Subpackets with the same tag
+    /// are then ordered by their serialized form, including the
+    /// critical bit and the length encoding.  As a result, the order
+    /// no longer depends on the order in which the subpackets were
+    /// added: two areas containing the same subpackets serialize to
+    /// the same bytes.  The sort is stable, and sorting an already
+    /// sorted area doesn't change it.
+    ///
+    /// This gives a deterministic subpacket order, but it doesn't
+    /// make signing reproducible: when a signature is created, the
+    /// [`SignatureBuilder`] adds a random salt notation to the hashed
+    /// area.  It is useful for comparing or normalizing subpacket
+    /// areas, e.g., of existing signatures.
+    ///
+    /// Note: this may reorder subpackets with the same tag.  If a
+    /// subpacket that should only occur once occurs several times,
+    /// this may change which instance is considered to be the last
+    /// one, see [`SubpacketArea::subpacket`].
+    ///
+    /// [`SubpacketArea::sort`]: Self::sort()
+    /// [`SignatureBuilder`]: super::SignatureBuilder
+    /// [`SubpacketArea::subpacket`]: Self::subpacket()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::subpacket::{
+    ///     NotationData,
+    ///     NotationDataFlags,
+    ///     Subpacket,
+    ///     SubpacketArea,
+    ///     SubpacketValue,
+    /// };
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let notation = |name| Subpacket::new(SubpacketValue::NotationData(
+    ///     NotationData::new(name, b"", NotationDataFlags::empty())), false);
+    ///
+    /// let mut a = SubpacketArea::default();
+    /// a.add(notation("b@example.org")?)?;
+    /// a.add(notation("a@example.org")?)?;
+    ///
+    /// let mut b = SubpacketArea::default();
+    /// b.add(notation("a@example.org")?)?;
+    /// b.add(notation("b@example.org")?)?;
+    ///
+    /// assert_ne!(a, b);
+    /// a.sort_canonical();
+    /// b.sort_canonical();
+    /// assert_eq!(a, b);
+    /// # Ok(()) }
+    /// ```
+    pub fn sort_canonical(&mut self) {
+        self.cache_invalidate();
+        // slice::sort_by_cached_key is stable.
+        self.packets.sort_by_cached_key(|sp| {
+            (u8::from(sp.tag()), sp.to_vec().unwrap_or_default())
+        });
+    }
 }
 
 /// Payload of a Notation Data subpacket.
@@ -1244,6 +2807,36 @@ impl NotationDataFlags {
         Self::new(&[0, 0, 0, 0]).unwrap()
     }
 
+    /// Creates a new instance from the flags' wire representation.
+    ///
+    /// The four octets of the notation flags are interpreted as a
+    /// big-endian number, hence the human-readable flag corresponds
+    /// to the most significant bit, `0x8000_0000`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::subpacket::NotationDataFlags;
+    ///
+    /// let ndf = NotationDataFlags::from_raw(0x8000_0000);
+    /// assert!(ndf.human_readable());
+    /// assert_eq!(ndf, NotationDataFlags::empty().set_human_readable());
+    /// assert_eq!(ndf.to_raw(), 0x8000_0000);
+    /// ```
+    pub fn from_raw(flags: u32) -> Self {
+        Self(flags.to_be_bytes().to_vec().into())
+    }
+
+    /// Returns the flags' wire representation as a big-endian number.
+    ///
+    /// This is the inverse of [`NotationDataFlags::from_raw`].
+    pub fn to_raw(&self) -> u32 {
+        let mut raw = [0; 4];
+        raw.copy_from_slice(&self.as_slice()[..4]);
+        u32::from_be_bytes(raw)
+    }
+
     /// Returns a slice containing the raw values.
     pub(crate) fn as_slice(&self) -> &[u8] {
         self.0.as_slice()
@@ -1594,6 +3187,13 @@ pub enum SubpacketValue {
     ///
     ///  [Section 5.2.3.30 of RFC 4880bis]: https://tools.ietf.org/html/draft-ietf-openpgp-rfc4880bis-10.html#section-5.2.3.30
     AttestedCertifications(Vec<Box<[u8]>>),
+    /// The AEAD ciphersuites that the certificate holder prefers
+    /// (proposed).
+    ///
+    /// See [Section 5.2.3.15 of RFC 9580] for details.
+    ///
+    ///  [Section 5.2.3.15 of RFC 9580]: https://www.rfc-editor.org/rfc/rfc9580.html#section-5.2.3.15
+    PreferredAEADCiphersuites(Vec<(SymmetricAlgorithm, AEADAlgorithm)>),
 }
 assert_send_and_sync!(SubpacketValue);
 
@@ -1604,7 +3204,7 @@ impl ArbitraryBounded for SubpacketValue {
         use crate::arbitrary_helper::gen_arbitrary_from_range;
 
         loop {
-            break match gen_arbitrary_from_range(0..26, g) {
+            break match gen_arbitrary_from_range(0..27, g) {
                 0 => SignatureCreationTime(Arbitrary::arbitrary(g)),
                 1 => SignatureExpirationTime(Arbitrary::arbitrary(g)),
                 2 => ExportableCertification(Arbitrary::arbitrary(g)),
@@ -1643,6 +3243,7 @@ impl ArbitraryBounded for SubpacketValue {
                 23 => IssuerFingerprint(Arbitrary::arbitrary(g)),
                 24 => PreferredAEADAlgorithms(Arbitrary::arbitrary(g)),
                 25 => IntendedRecipient(Arbitrary::arbitrary(g)),
+                26 => PreferredAEADCiphersuites(Arbitrary::arbitrary(g)),
                 _ => unreachable!(),
             }
         }
@@ -1690,11 +3291,22 @@ impl SubpacketValue {
                 SubpacketTag::PreferredAEADAlgorithms,
             IntendedRecipient(_) => SubpacketTag::IntendedRecipient,
             AttestedCertifications(_) => SubpacketTag::AttestedCertifications,
+            PreferredAEADCiphersuites(_) =>
+                SubpacketTag::PreferredAEADCiphersuites,
             Unknown { tag, .. } => *tag,
         }
     }
 }
 
+impl From<Signature> for SubpacketValue {
+    /// Returns an [Embedded Signature subpacket] value holding `sig`.
+    ///
+    ///   [Embedded Signature subpacket]: https://tools.ietf.org/html/rfc4880#section-5.2.3.26
+    fn from(sig: Signature) -> Self {
+        SubpacketValue::EmbeddedSignature(sig)
+    }
+}
+
 /// Signature subpackets.
 ///
 /// Most of a signature's attributes are not stored in fixed fields,
@@ -1891,6 +3503,43 @@ impl Subpacket {
         &mut self.value
     }
 
+    /// Returns whether the subpacket's value has been understood.
+    ///
+    /// This returns `false` if the value is a
+    /// [`SubpacketValue::Unknown`], i.e., if the subpacket has a tag
+    /// that Sequoia doesn't know about, or if it is
+    /// [malformed](Self::is_malformed()).  Otherwise, the value has
+    /// been fully parsed, and this returns `true`.
+    pub fn is_well_known(&self) -> bool {
+        ! matches!(self.value, SubpacketValue::Unknown { .. })
+    }
+
+    /// Returns whether the subpacket is malformed.
+    ///
+    /// A subpacket is malformed if its tag is one of the
+    /// standardized tags, but its value is a
+    /// [`SubpacketValue::Unknown`], i.e., the body is stored
+    /// uninterpreted rather than as the value that the tag calls for.
+    /// Subpackets with reserved, private, or unknown tags are never
+    /// considered malformed.
+    ///
+    /// The parser doesn't produce such subpackets: if the body of a
+    /// subpacket with a standardized tag can't be parsed, parsing the
+    /// whole signature fails.  Hence, this only returns `true` for
+    /// subpackets that were constructed by hand, e.g., using
+    /// [`Subpacket::new`].
+    pub fn is_malformed(&self) -> bool {
+        match self.value {
+            SubpacketValue::Unknown { tag, .. } => ! matches!(
+                tag,
+                SubpacketTag::Reserved(_)
+                    | SubpacketTag::PlaceholderForBackwardCompatibility
+                    | SubpacketTag::Private(_)
+                    | SubpacketTag::Unknown(_)),
+            _ => false,
+        }
+    }
+
     /// Returns whether the information in this subpacket has been
     /// authenticated.
     ///
@@ -2013,6 +3662,46 @@ impl SubpacketLength {
     }
 }
 
+/// Classifies the trust amount of a [Trust Signature subpacket].
+///
+/// By convention, an amount of 120 or more means that a
+/// certification should be considered valid, and a smaller amount
+/// means that it should only be considered partially valid.
+///
+/// [Trust Signature subpacket]: https://tools.ietf.org/html/rfc4880#section-5.2.3.13
+///
+/// # Examples
+///
+/// ```
+/// # use sequoia_openpgp as openpgp;
+/// use openpgp::packet::signature::subpacket::TrustLevel;
+///
+/// assert_eq!(TrustLevel::from_amount(60), TrustLevel::Partial);
+/// assert_eq!(TrustLevel::from_amount(120), TrustLevel::Complete);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrustLevel {
+    /// The amount is less than 120.
+    Partial,
+    /// The amount is at least 120.
+    Complete,
+}
+assert_send_and_sync!(TrustLevel);
+
+impl TrustLevel {
+    /// The smallest amount that is considered complete trust.
+    pub const COMPLETE: u8 = 120;
+
+    /// Classifies the trust `amount`.
+    pub fn from_amount(amount: u8) -> Self {
+        if amount >= Self::COMPLETE {
+            TrustLevel::Complete
+        } else {
+            TrustLevel::Partial
+        }
+    }
+}
+
 /// Subpacket storage.
 ///
 /// Subpackets are stored either in a so-called hashed area or a
@@ -2127,6 +3816,16 @@ impl SubpacketAreas {
         self.unhashed_area().subpacket(tag)
     }
 
+    /// Returns whether the specified subpacket is present.
+    ///
+    /// This considers the same subpacket areas as
+    /// [`SubpacketAreas::subpacket`]: the hashed area, and for the
+    /// Issuer, Issuer Fingerprint, and Embedded Signature subpackets
+    /// also the unhashed area.
+    pub fn has_subpacket(&self, tag: SubpacketTag) -> bool {
+        self.subpacket(tag).is_some()
+    }
+
     /// Returns a mutable reference to the *last* instance of the
     /// specified subpacket, if any.
     ///
@@ -2206,18 +3905,117 @@ impl SubpacketAreas {
             }))
     }
 
-    pub(crate) fn subpackets_mut(&mut self, tag: SubpacketTag)
-        -> impl Iterator<Item = &mut Subpacket> + Send + Sync
-    {
-        self.hashed_area.subpackets_mut(tag).chain(
-            self.unhashed_area
-                .iter_mut()
-                .filter(move |sp| {
-                    (tag == SubpacketTag::Issuer
-                     || tag == SubpacketTag::IssuerFingerprint
-                     || tag == SubpacketTag::EmbeddedSignature)
-                        && sp.tag() == tag
-            }))
+    pub(crate) fn subpackets_mut(&mut self, tag: SubpacketTag)
+        -> impl Iterator<Item = &mut Subpacket> + Send + Sync
+    {
+        self.hashed_area.subpackets_mut(tag).chain(
+            self.unhashed_area
+                .iter_mut()
+                .filter(move |sp| {
+                    (tag == SubpacketTag::Issuer
+                     || tag == SubpacketTag::IssuerFingerprint
+                     || tag == SubpacketTag::EmbeddedSignature)
+                        && sp.tag() == tag
+            }))
+    }
+
+    /// Returns the number of subpackets in both subpacket areas.
+    pub fn subpacket_count(&self) -> usize {
+        self.hashed_area().iter().count() + self.unhashed_area().iter().count()
+    }
+
+    /// Returns the number of occurrences of each subpacket tag.
+    ///
+    /// Unlike [`SubpacketAreas::subpackets`], this counts the
+    /// subpackets in both the hashed and the unhashed subpacket
+    /// area, regardless of whether they are allowed to occur in the
+    /// unhashed area.  This is intended for inspecting signatures.
+    ///
+    /// [`SubpacketAreas::subpackets`]: Self::subpackets()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::packet::signature::subpacket::SubpacketTag;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    /// let (cert, _) = CertBuilder::new().generate()?;
+    /// let sig = cert.with_policy(p, None)?.direct_key_signature()?;
+    ///
+    /// let tags = sig.subpacket_tags();
+    /// assert_eq!(tags.get(&SubpacketTag::SignatureCreationTime), Some(&1));
+    /// assert_eq!(tags.values().sum::<usize>(), sig.subpacket_count());
+    /// # Ok(()) }
+    /// ```
+    pub fn subpacket_tags(&self) -> BTreeMap<SubpacketTag, usize> {
+        let mut tags = BTreeMap::new();
+        for sp in self.hashed_area().iter().chain(self.unhashed_area().iter()) {
+            *tags.entry(sp.tag()).or_insert(0) += 1;
+        }
+        tags
+    }
+
+    /// Returns the tags of subpackets in the unhashed area that
+    /// should be in the hashed area.
+    ///
+    /// The unhashed subpacket area is not protected by the
+    /// signature.  Apart from the `Issuer`, `Issuer Fingerprint`, and
+    /// `Embedded Signature` subpackets, which are self-authenticating,
+    /// any subpacket in the unhashed area can be added or modified by
+    /// an attacker, and is ignored by lookups like
+    /// [`SubpacketAreas::subpacket`].  This function lists such
+    /// subpackets, so that a verifier can reject signatures that
+    /// carry, e.g., key flags in the unhashed area.
+    ///
+    /// Each tag is returned once, in the order of first appearance.
+    ///
+    /// [`SubpacketAreas::subpacket`]: Self::subpacket()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use openpgp::packet::signature::subpacket::{
+    ///     Subpacket,
+    ///     SubpacketTag,
+    ///     SubpacketValue,
+    /// };
+    /// use openpgp::policy::StandardPolicy;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    /// let (cert, _) = CertBuilder::new().generate()?;
+    /// let mut sig = cert.with_policy(p, None)?.direct_key_signature()?.clone();
+    /// assert!(sig.unprotected_security_subpackets().is_empty());
+    ///
+    /// sig.unhashed_area_mut().add(Subpacket::new(
+    ///     SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()),
+    ///     false)?)?;
+    /// assert_eq!(sig.unprotected_security_subpackets(),
+    ///            vec![ SubpacketTag::KeyFlags ]);
+    /// # Ok(()) }
+    /// ```
+    pub fn unprotected_security_subpackets(&self) -> Vec<SubpacketTag> {
+        let mut tags = Vec::new();
+        for sp in self.unhashed_area().iter() {
+            let tag = sp.tag();
+            if tag == SubpacketTag::Issuer
+                || tag == SubpacketTag::IssuerFingerprint
+                || tag == SubpacketTag::EmbeddedSignature
+            {
+                continue;
+            }
+            if ! tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
     }
 
     /// Returns the value of the Signature Creation Time subpacket.
@@ -2250,6 +4048,46 @@ impl SubpacketAreas {
         }
     }
 
+    /// Returns whether the signature's creation time lies within
+    /// the given window.
+    ///
+    /// The window is inclusive, i.e. this returns true if the value
+    /// of the Signature Creation Time subpacket is neither before
+    /// `earliest` nor after `latest`.  If the signature doesn't have
+    /// a Signature Creation Time subpacket in the hashed area, this
+    /// returns false.
+    ///
+    /// This is useful when checking, e.g., timestamp signatures
+    /// issued by a trusted timestamping service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use sequoia_openpgp as openpgp;
+    /// # use openpgp::cert::prelude::*;
+    /// use std::time::{Duration, SystemTime};
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    /// let (cert, _) = CertBuilder::new().generate()?;
+    /// let sig = cert.with_policy(p, None)?.direct_key_signature()?;
+    ///
+    /// let now = SystemTime::now();
+    /// let hour = Duration::new(3600, 0);
+    /// assert!(sig.creation_time_in_range(now - hour, now));
+    /// assert!(! sig.creation_time_in_range(now - 2 * hour, now - hour));
+    /// # Ok(()) }
+    /// ```
+    pub fn creation_time_in_range(&self, earliest: time::SystemTime,
+                                  latest: time::SystemTime)
+                                  -> bool
+    {
+        self.signature_creation_time()
+            .map(|t| earliest <= t && t <= latest)
+            .unwrap_or(false)
+    }
+
     /// Returns the value of the Signature Expiration Time subpacket.
     ///
     /// This function is called `signature_validity_period` and not
@@ -2372,7 +4210,9 @@ impl SubpacketAreas {
     ///
     /// If the subpacket is not present in the hashed subpacket area,
     /// this returns `None`.  If this function returns `None`, the
-    /// signature does not expire.
+    /// signature does not expire.  This function also returns `None`
+    /// if the expiration time cannot be represented as a
+    /// `SystemTime` on this platform.
     ///
     /// Note: if the signature contains multiple instances of this
     /// subpacket in the hashed subpacket area, the last one is
@@ -2380,7 +4220,7 @@ impl SubpacketAreas {
     pub fn signature_expiration_time(&self) -> Option<time::SystemTime> {
         match (self.signature_creation_time(), self.signature_validity_period())
         {
-            (Some(ct), Some(vp)) if vp.as_secs() > 0 => Some(ct + vp),
+            (Some(ct), Some(vp)) if vp.as_secs() > 0 => ct.checked_add(vp),
             _ => None,
         }
     }
@@ -2639,6 +4479,58 @@ impl SubpacketAreas {
         }
     }
 
+    /// Returns the time at which the signature, or the key it binds,
+    /// expires, whichever comes first.
+    ///
+    /// This combines [`SubpacketAreas::signature_expiration_time`]
+    /// and [`SubpacketAreas::key_expiration_time`].  Since the Key
+    /// Expiration Time subpacket is relative to the key's creation
+    /// time, `key_creation` must be the creation time of the key that
+    /// this binding signature is associated with.  If neither
+    /// subpacket is present (or both are zero), this returns `None`.
+    ///
+    /// If adding a validity period to the corresponding creation time
+    /// overflows `SystemTime`, that expiration time is treated as
+    /// lying beyond any representable time, i.e. it is ignored.
+    ///
+    /// [`SubpacketAreas::signature_expiration_time`]: Self::signature_expiration_time()
+    /// [`SubpacketAreas::key_expiration_time`]: Self::key_expiration_time()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// # use sequoia_openpgp as openpgp;
+    /// use openpgp::cert::prelude::*;
+    /// use openpgp::policy::StandardPolicy;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let p = &StandardPolicy::new();
+    /// let (cert, _) = CertBuilder::new()
+    ///     .set_validity_period(Duration::new(3600, 0))
+    ///     .generate()?;
+    ///
+    /// let pk = cert.primary_key().key();
+    /// let sig = cert.primary_key().with_policy(p, None)?.binding_signature();
+    /// assert_eq!(sig.effective_expiration_time(pk.creation_time()),
+    ///            sig.key_expiration_time(pk));
+    /// # Ok(()) }
+    /// ```
+    pub fn effective_expiration_time(&self, key_creation: time::SystemTime)
+                                     -> Option<time::SystemTime>
+    {
+        let key_expiration = match self.key_validity_period() {
+            Some(vp) if vp.as_secs() > 0 => key_creation.checked_add(vp),
+            _ => None,
+        };
+
+        match (self.signature_expiration_time(), key_expiration) {
+            (Some(s), Some(k)) => Some(cmp::min(s, k)),
+            (Some(s), None) => Some(s),
+            (None, k) => k,
+        }
+    }
+
     /// Returns whether or not a key is alive at the specified
     /// time.
     ///
@@ -2789,6 +4681,28 @@ impl SubpacketAreas {
         }
     }
 
+    /// Returns the depth of the Trust Signature subpacket.
+    ///
+    /// This is the first element of the tuple returned by
+    /// [`SubpacketAreas::trust_signature`].
+    ///
+    /// [`SubpacketAreas::trust_signature`]: Self::trust_signature()
+    pub fn trust_depth(&self) -> Option<u8> {
+        self.trust_signature().map(|(depth, _)| depth)
+    }
+
+    /// Returns the amount of the Trust Signature subpacket.
+    ///
+    /// This is the second element of the tuple returned by
+    /// [`SubpacketAreas::trust_signature`].  Use
+    /// [`TrustLevel::from_amount`] to classify it.
+    ///
+    /// [`SubpacketAreas::trust_signature`]: Self::trust_signature()
+    /// [`TrustLevel::from_amount`]: TrustLevel::from_amount()
+    pub fn trust_amount(&self) -> Option<u8> {
+        self.trust_signature().map(|(_, amount)| amount)
+    }
+
     /// Returns the values of all Regular Expression subpackets.
     ///
     /// The [Regular Expression subpacket] is used in conjunction with
@@ -3214,6 +5128,44 @@ impl SubpacketAreas {
         }
     }
 
+    /// Returns the value of the Preferred AEAD Ciphersuites
+    /// subpacket.
+    ///
+    /// The [Preferred AEAD Ciphersuites subpacket] indicates what
+    /// combinations of symmetric algorithms and AEAD modes the key
+    /// holder prefers ordered by preference.  Like the [Preferred
+    /// AEAD Algorithms subpacket], it is a type of preference, see
+    /// [`SubpacketAreas::preferred_aead_algorithms`] for how to look
+    /// it up.
+    ///
+    /// [Preferred AEAD Ciphersuites subpacket]: https://www.rfc-editor.org/rfc/rfc9580.html#section-5.2.3.15
+    /// [Preferred AEAD Algorithms subpacket]: https://tools.ietf.org/html/draft-ietf-openpgp-rfc4880bis-09.html#section-5.2.3.8
+    /// [`SubpacketAreas::preferred_aead_algorithms`]: Self::preferred_aead_algorithms()
+    ///
+    /// If the subpacket is not present in the hashed subpacket area,
+    /// this returns `None`.
+    ///
+    /// Note: if the signature contains multiple instances of this
+    /// subpacket in the hashed subpacket area, the last one is
+    /// returned.
+    pub fn preferred_aead_ciphersuites(&self)
+        -> Option<&[(SymmetricAlgorithm, AEADAlgorithm)]>
+    {
+        // array of pairs of one-octet values
+        if let Some(sb)
+                = self.subpacket(
+                    SubpacketTag::PreferredAEADCiphersuites) {
+            if let SubpacketValue::PreferredAEADCiphersuites(v)
+                    = &sb.value {
+                Some(v)
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
     /// Returns the value of the Key Server Preferences subpacket.
     ///
     /// The [Key Server Preferences subpacket] indicates to key
@@ -4100,6 +6052,115 @@ impl signature::SignatureBuilder {
         Ok(self)
     }
 
+    /// Adds the given subpackets to the hashed subpacket area.
+    ///
+    /// This is useful when the set of subpackets is computed at
+    /// runtime.  Each value is turned into a subpacket with the given
+    /// criticality, and the result is merged into the hashed area
+    /// using [`SubpacketArea::merge`]: subpackets that may occur
+    /// multiple times, like [Notation Data subpackets], are added,
+    /// all others replace any existing subpackets with the same tag.
+    ///
+    /// Note: the Signature Creation Time subpacket should be set
+    /// using [`SignatureBuilder::set_signature_creation_time`],
+    /// otherwise it is overridden when the signature is created.
+    ///
+    /// [Notation Data subpackets]: https://tools.ietf.org/html/rfc4880#section-5.2.3.16
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MalformedPacket` if the hashed area would
+    /// exceed the size limit.  In that case, the hashed area is not
+    /// modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::SignatureBuilder;
+    /// use openpgp::packet::signature::subpacket::{SubpacketTag, SubpacketValue};
+    /// use openpgp::types::{KeyFlags, SignatureType};
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let values = vec![
+    ///     SubpacketValue::KeyFlags(KeyFlags::empty().set_signing()),
+    ///     SubpacketValue::Revocable(false),
+    /// ];
+    ///
+    /// let builder = SignatureBuilder::new(SignatureType::DirectKey)
+    ///     .merge_hashed_subpackets(values, false)?;
+    /// assert!(builder.hashed_area().contains(SubpacketTag::KeyFlags));
+    /// assert_eq!(builder.revocable(), Some(false));
+    /// # Ok(()) }
+    /// ```
+    pub fn merge_hashed_subpackets<I>(mut self, values: I, critical: bool)
+        -> Result<Self>
+        where I: IntoIterator<Item = SubpacketValue>
+    {
+        let mut area = SubpacketArea::default();
+        for value in values {
+            area.add(Subpacket::new(value, critical)?)?;
+        }
+        self.fields.subpackets.hashed_area.merge(&area)?;
+        Ok(self)
+    }
+
+    /// Sets a private or experimental subpacket.
+    ///
+    /// Subpacket tags 100 to 110 are reserved for [private or
+    /// experimental use].  This adds a subpacket with the given `tag`
+    /// and the uninterpreted `value` to the hashed subpacket area,
+    /// replacing any existing subpackets with the same tag.  This is
+    /// useful to prototype new subpackets.
+    ///
+    /// When parsed, such subpackets are represented as
+    /// [`SubpacketValue::Unknown`] with a [`SubpacketTag::Private`]
+    /// tag.
+    ///
+    /// [private or experimental use]: https://tools.ietf.org/html/rfc4880#section-5.2.3.1
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidArgument` if `tag` is not in the range
+    /// 100 to 110.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::SignatureBuilder;
+    /// use openpgp::packet::signature::subpacket::{SubpacketTag, SubpacketValue};
+    /// use openpgp::types::SignatureType;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let builder = SignatureBuilder::new(SignatureType::Binary)
+    ///     .set_private_subpacket(101, b"experiment".to_vec(), false)?;
+    /// assert_eq!(builder.subpacket(SubpacketTag::Private(101)).unwrap().value(),
+    ///            &SubpacketValue::Unknown {
+    ///                tag: SubpacketTag::Private(101),
+    ///                body: b"experiment".to_vec(),
+    ///            });
+    ///
+    /// assert!(SignatureBuilder::new(SignatureType::Binary)
+    ///         .set_private_subpacket(42, vec![], false).is_err());
+    /// # Ok(()) }
+    /// ```
+    pub fn set_private_subpacket(mut self, tag: u8, value: Vec<u8>,
+                                 critical: bool)
+        -> Result<Self>
+    {
+        let tag = match SubpacketTag::from(tag) {
+            t @ SubpacketTag::Private(_) => t,
+            _ => return Err(Error::InvalidArgument(
+                format!("Subpacket tag {} is not in the private range \
+                         (100..=110)", tag)).into()),
+        };
+
+        self.hashed_area.replace(Subpacket::new(
+            SubpacketValue::Unknown { tag, body: value }, critical)?)?;
+        Ok(self)
+    }
+
     /// Sets the Signature Creation Time subpacket.
     ///
     /// Adds a [Signature Creation Time subpacket] to the hashed
@@ -4121,6 +6182,16 @@ impl signature::SignatureBuilder {
     /// [Signature Creation Time subpacket]: https://tools.ietf.org/html/rfc4880#section-5.2.3.4
     /// [streaming `Signer`]: crate::serialize::stream::Signer
     ///
+    /// # Errors
+    ///
+    /// OpenPGP timestamps are unsigned 32-bit numbers of seconds
+    /// since the Unix epoch.  This function returns
+    /// [`Error::InvalidArgument`] if `creation_time` predates the
+    /// Unix epoch, or if it is after the last representable time (in
+    /// the year 2106).
+    ///
+    /// [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+    ///
     /// # Examples
     ///
     /// Create a backdated signature:
@@ -4300,6 +6371,112 @@ impl signature::SignatureBuilder {
         Ok(self)
     }
 
+    /// Causes the builder to not add any issuer information.
+    ///
+    /// Normally, when a `SignatureBuilder` is finalized and it
+    /// contains neither an [Issuer subpacket] nor an [Issuer
+    /// Fingerprint subpacket], both are added using the signer's
+    /// Key ID and fingerprint.  This function suppresses that, and
+    /// removes any existing Issuer and Issuer Fingerprint subpackets
+    /// from both subpacket areas.
+    ///
+    /// [Issuer subpacket]: https://tools.ietf.org/html/rfc4880#section-5.2.3.5
+    /// [Issuer Fingerprint subpacket]: https://tools.ietf.org/html/draft-ietf-openpgp-rfc4880bis-09#section-5.2.3.28
+    ///
+    /// **Warning**: Without issuer information, a recipient has no
+    /// way to tell which key made the signature other than trying
+    /// to verify it with every candidate key.  Many implementations
+    /// (including Sequoia's streaming [`Verifier`]) won't even try,
+    /// and will consider such a signature to be unverifiable.  Only
+    /// use this if you need to hide the signer's identity, and the
+    /// recipient knows which keys to try.
+    ///
+    /// [`Verifier`]: crate::parse::stream::Verifier
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::cert::prelude::*;
+    /// use openpgp::packet::signature::SignatureBuilder;
+    /// use openpgp::types::SignatureType;
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// #
+    /// # let (cert, _) =
+    /// #     CertBuilder::general_purpose(None, Some("alice@example.org"))
+    /// #     .generate()?;
+    /// # let mut signer = cert.primary_key().key().clone()
+    /// #     .parts_into_secret()?.into_keypair()?;
+    /// let msg = "Guess who.";
+    ///
+    /// let mut sig = SignatureBuilder::new(SignatureType::Binary)
+    ///     .without_issuer()
+    ///     .sign_message(&mut signer, msg)?;
+    /// assert!(sig.get_issuers().is_empty());
+    ///
+    /// // The signature is still valid.
+    /// sig.verify_message(signer.public(), msg)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn without_issuer(mut self) -> Self {
+        self.suppress_issuer = true;
+
+        self.hashed_area.remove_all(SubpacketTag::Issuer);
+        self.hashed_area.remove_all(SubpacketTag::IssuerFingerprint);
+        self.unhashed_area.remove_all(SubpacketTag::Issuer);
+        self.unhashed_area.remove_all(SubpacketTag::IssuerFingerprint);
+
+        self
+    }
+
+    /// Enables strict mode.
+    ///
+    /// By default, the setters accept empty values, like an empty
+    /// list of preferred algorithms, and happily emit subpackets with
+    /// zero-length bodies.  Although this is technically valid, it is
+    /// rarely what the caller intended.  In strict mode, the setters
+    /// for the Preferred Symmetric Algorithms, Preferred Hash
+    /// Algorithms, Preferred Compression Algorithms, Preferred AEAD
+    /// Algorithms, Preferred AEAD Ciphersuites, and Policy URI
+    /// subpackets reject empty values with [`Error::InvalidArgument`].
+    ///
+    /// Strict mode only affects setters that are called after this
+    /// function.
+    ///
+    /// [`Error::InvalidArgument`]: crate::Error::InvalidArgument
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::SignatureBuilder;
+    /// use openpgp::types::SignatureType;
+    ///
+    /// // By default, empty preferences are accepted.
+    /// assert!(SignatureBuilder::new(SignatureType::DirectKey)
+    ///     .set_preferred_symmetric_algorithms(vec![]).is_ok());
+    ///
+    /// // In strict mode, they are rejected.
+    /// assert!(SignatureBuilder::new(SignatureType::DirectKey)
+    ///     .strict()
+    ///     .set_preferred_symmetric_algorithms(vec![]).is_err());
+    /// ```
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// In strict mode, returns an error if `empty` is true.
+    fn check_not_empty(&self, empty: bool, what: &str) -> Result<()> {
+        if self.strict && empty {
+            Err(Error::InvalidArgument(
+                format!("{} must not be empty", what)).into())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Sets the Signature Expiration Time subpacket.
     ///
     /// Adds a [Signature Expiration Time subpacket] to the hashed
@@ -5188,6 +7365,8 @@ impl signature::SignatureBuilder {
     pub fn set_preferred_symmetric_algorithms(mut self,
                                               preferences: Vec<SymmetricAlgorithm>)
                                               -> Result<Self> {
+        self.check_not_empty(preferences.is_empty(),
+                             "Preferred symmetric algorithms")?;
         self.hashed_area.replace(Subpacket::new(
             SubpacketValue::PreferredSymmetricAlgorithms(preferences),
             false)?)?;
@@ -5702,6 +7881,8 @@ impl signature::SignatureBuilder {
     pub fn set_preferred_hash_algorithms(mut self,
                                          preferences: Vec<HashAlgorithm>)
                                          -> Result<Self> {
+        self.check_not_empty(preferences.is_empty(),
+                             "Preferred hash algorithms")?;
         self.hashed_area.replace(Subpacket::new(
             SubpacketValue::PreferredHashAlgorithms(preferences),
             false)?)?;
@@ -5783,6 +7964,8 @@ impl signature::SignatureBuilder {
     pub fn set_preferred_compression_algorithms(mut self,
                                                 preferences: Vec<CompressionAlgorithm>)
                                                 -> Result<Self> {
+        self.check_not_empty(preferences.is_empty(),
+                             "Preferred compression algorithms")?;
         self.hashed_area.replace(Subpacket::new(
             SubpacketValue::PreferredCompressionAlgorithms(preferences),
             false)?)?;
@@ -5900,6 +8083,11 @@ impl signature::SignatureBuilder {
     ///
     /// [`Preferences`]: crate::cert::Preferences
     ///
+    /// The URI is not parsed.  But, to catch obvious mistakes, this
+    /// function returns an error if `uri` is empty or contains
+    /// anything other than printable ASCII characters, which are the
+    /// only characters that may appear in a URI.
+    ///
     /// # Examples
     ///
     /// ```
@@ -5940,8 +8128,17 @@ impl signature::SignatureBuilder {
                                        -> Result<Self>
         where U: AsRef<[u8]>,
     {
+        let uri = uri.as_ref();
+        if uri.is_empty()
+            || uri.iter().any(|&c| ! (c as char).is_ascii_graphic())
+        {
+            return Err(Error::InvalidArgument(format!(
+                "Preferred key server is not a plausible URI: {}",
+                String::from_utf8_lossy(uri))).into());
+        }
+
         self.hashed_area.replace(Subpacket::new(
-            SubpacketValue::PreferredKeyServer(uri.as_ref().to_vec()),
+            SubpacketValue::PreferredKeyServer(uri.to_vec()),
             false)?)?;
 
         Ok(self)
@@ -6104,6 +8301,7 @@ impl signature::SignatureBuilder {
     pub fn set_policy_uri<U>(mut self, uri: U) -> Result<Self>
         where U: AsRef<[u8]>,
     {
+        self.check_not_empty(uri.as_ref().is_empty(), "Policy URI")?;
         self.hashed_area.replace(Subpacket::new(
             SubpacketValue::PolicyURI(uri.as_ref().to_vec()),
             false)?)?;
@@ -6787,6 +8985,8 @@ impl signature::SignatureBuilder {
                                          preferences: Vec<AEADAlgorithm>)
         -> Result<Self>
     {
+        self.check_not_empty(preferences.is_empty(),
+                             "Preferred AEAD algorithms")?;
         self.hashed_area.replace(Subpacket::new(
             SubpacketValue::PreferredAEADAlgorithms(preferences),
             false)?)?;
@@ -6794,6 +8994,52 @@ impl signature::SignatureBuilder {
         Ok(self)
     }
 
+    /// Sets the Preferred AEAD Ciphersuites subpacket.
+    ///
+    /// Replaces any [Preferred AEAD Ciphersuites subpacket] in the
+    /// hashed subpacket area with a new subpacket containing the
+    /// specified value.  That is, this function first removes any
+    /// Preferred AEAD Ciphersuites subpacket from the hashed
+    /// subpacket area, and then adds a new one.
+    ///
+    /// A ciphersuite is a pair of a symmetric algorithm and an AEAD
+    /// mode.  The ciphersuites are ordered by preference.  As with
+    /// [`SignatureBuilder::set_preferred_aead_algorithms`], the AEAD
+    /// feature flag in the Features subpacket should also be set.
+    ///
+    /// [Preferred AEAD Ciphersuites subpacket]: https://www.rfc-editor.org/rfc/rfc9580.html#section-5.2.3.15
+    /// [`SignatureBuilder::set_preferred_aead_algorithms`]: Self::set_preferred_aead_algorithms()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::packet::signature::SignatureBuilder;
+    /// use openpgp::types::{AEADAlgorithm, SignatureType, SymmetricAlgorithm};
+    ///
+    /// # fn main() -> openpgp::Result<()> {
+    /// let suites = [
+    ///     (SymmetricAlgorithm::AES256, AEADAlgorithm::OCB),
+    ///     (SymmetricAlgorithm::AES128, AEADAlgorithm::EAX),
+    /// ];
+    /// let builder = SignatureBuilder::new(SignatureType::DirectKey)
+    ///     .set_preferred_aead_ciphersuites(&suites)?;
+    /// assert_eq!(builder.preferred_aead_ciphersuites(), Some(&suites[..]));
+    /// # Ok(()) }
+    /// ```
+    pub fn set_preferred_aead_ciphersuites(
+        mut self, pairs: &[(SymmetricAlgorithm, AEADAlgorithm)])
+        -> Result<Self>
+    {
+        self.check_not_empty(pairs.is_empty(),
+                             "Preferred AEAD ciphersuites")?;
+        self.hashed_area.replace(Subpacket::new(
+            SubpacketValue::PreferredAEADCiphersuites(pairs.to_vec()),
+            false)?)?;
+
+        Ok(self)
+    }
+
     /// Sets the Intended Recipient subpacket.
     ///
     /// Replaces any [Intended Recipient subpacket] in the hashed
@@ -1730,6 +1730,17 @@ impl Subpacket {
                 SubpacketValue::PreferredAEADAlgorithms(
                     php.parse_bytes("pref aead algos", len)?
                         .iter().map(|o| (*o).into()).collect()),
+            SubpacketTag::PreferredAEADCiphersuites => {
+                if len % 2 != 0 {
+                    return Err(Error::MalformedPacket(
+                        format!("Malformed preferred AEAD ciphersuites \
+                                 subpacket: odd length {}", len)).into());
+                }
+                SubpacketValue::PreferredAEADCiphersuites(
+                    php.parse_bytes("pref aead suites", len)?
+                        .chunks(2).map(|p| (p[0].into(), p[1].into()))
+                        .collect())
+            },
             SubpacketTag::IntendedRecipient => {
                 if len == 0 {
                     return Err(Error::MalformedPacket(
@@ -635,7 +635,7 @@ a_cutoff_list!(SecondPreImageResistantHashCutoffList, HashAlgorithm, 12,
                    ACCEPT,                   // 11. SHA224
                ]);
 
-a_cutoff_list!(SubpacketTagCutoffList, SubpacketTag, 38,
+a_cutoff_list!(SubpacketTagCutoffList, SubpacketTag, 40,
                [
                    REJECT,                 // 0. Reserved.
                    REJECT,                 // 1. Reserved.
@@ -678,6 +678,8 @@ a_cutoff_list!(SubpacketTagCutoffList, SubpacketTag, 38,
                    ACCEPT,                 // 35. IntendedRecipient.
                    REJECT,                 // 36. Reserved.
                    ACCEPT,                 // 37. AttestedCertifications.
+                   REJECT,                 // 38. Reserved.
+                   ACCEPT,                 // 39. PreferredAEADCiphersuites.
                ]);
 
 a_cutoff_list!(AsymmetricAlgorithmCutoffList, AsymmetricAlgorithm, 18,
@@ -1373,7 +1373,7 @@ impl Marshal for Subpacket {
         let tag = u8::from(self.tag())
             | if self.critical() { 1 << 7 } else { 0 };
 
-        self.length.serialize(o)?;
+        self.length.checked_serialize(o)?;
         o.write_all(&[tag])?;
         self.value().serialize(o)
     }
@@ -1480,6 +1480,10 @@ impl Marshal for SubpacketValue {
                     o.write_all(digest)?;
                 }
             },
+            PreferredAEADCiphersuites(ref p) =>
+                for (s, a) in p {
+                    o.write_all(&[(*s).into(), (*a).into()])?;
+                },
             Unknown { body, .. } =>
                 o.write_all(body)?,
         }
@@ -1529,6 +1533,7 @@ impl MarshalInto for SubpacketValue {
             },
             AttestedCertifications(digests) =>
                 digests.iter().map(|d| d.len()).sum(),
+            PreferredAEADCiphersuites(ref p) => 2 * p.len(),
             Unknown { body, .. } => body.len(),
         }
     }
@@ -1569,6 +1574,32 @@ impl MarshalInto for SubpacketLength {
     }
 }
 
+impl SubpacketLength {
+    /// Writes the subpacket length to `sink`, checking that the
+    /// subpacket fits into a subpacket area.
+    ///
+    /// The length covers the subpacket's tag and value.  If the
+    /// length, tag, and value together exceed
+    /// [`SubpacketArea::MAX_SIZE`], this returns
+    /// `Error::MalformedPacket` without writing anything.
+    ///
+    /// `SubpacketLength` is not part of the public API.  Users get
+    /// this check when serializing a [`Subpacket`], which uses this
+    /// function to write the length.
+    pub(crate) fn checked_serialize(&self, sink: &mut dyn std::io::Write)
+                                    -> Result<()> {
+        if MarshalInto::serialized_len(self) + self.len()
+            > SubpacketArea::MAX_SIZE
+        {
+            return Err(Error::MalformedPacket(
+                format!("Subpacket of {} bytes exceeds maximum size",
+                        self.len())).into());
+        }
+
+        self.serialize(sink)
+    }
+}
+
 
 impl seal::Sealed for RevocationKey {}
 impl Marshal for RevocationKey {
@@ -3491,4 +3522,89 @@ mod test {
             true
         }
     }
+
+    #[test]
+    fn signature_serialized_len() -> Result<()> {
+        use crate::cert::prelude::*;
+        use crate::packet::signature::subpacket::NotationDataFlags;
+
+        let (cert, _) = CertBuilder::new()
+            .add_userid("Alice <alice@example.org>")
+            .add_signing_subkey()
+            .generate()?;
+        let mut keypair = cert.primary_key().key().clone().parts_into_secret()?
+            .into_keypair()?;
+
+        // Include a large signature, which needs a longer header.
+        let big = UserID::from("bob").bind(
+            &mut keypair, &cert,
+            signature::SignatureBuilder::new(SignatureType::GenericCertification)
+                .add_notation("big@example.org", vec![0; 1000],
+                              NotationDataFlags::empty(), false)?)?;
+
+        for sig in cert.clone().into_packets()
+            .filter_map(|p| if let Packet::Signature(s) = p {
+                Some(s)
+            } else {
+                None
+            })
+            .chain(std::iter::once(big))
+        {
+            // The body.
+            let body = sig.to_vec()?;
+            assert_eq!(MarshalInto::serialized_len(&sig), body.len());
+
+            // The body plus the header.
+            let header_len = 1 + MarshalInto::serialized_len(
+                &BodyLength::Full(body.len() as u32));
+            let p = Packet::from(sig);
+            let len = MarshalInto::serialized_len(&p);
+            assert_eq!(len, MarshalInto::to_vec(&p)?.len());
+            assert_eq!(len, body.len() + header_len);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn subpacket_length_checked_serialize() {
+        use crate::packet::signature::subpacket::SubpacketLength;
+
+        // A subpacket that just fits: 5 octets of length, and 65530
+        // octets of tag and value.
+        let mut buf = Vec::new();
+        let l = SubpacketLength::from(
+            (SubpacketArea::MAX_SIZE - 5) as u32);
+        assert_eq!(l.serialized_len(), 5);
+        l.checked_serialize(&mut buf).unwrap();
+        assert_eq!(buf, l.to_vec().unwrap());
+
+        // One more octet doesn't.
+        let mut buf = Vec::new();
+        let l = SubpacketLength::from(
+            (SubpacketArea::MAX_SIZE - 4) as u32);
+        let err = l.checked_serialize(&mut buf).unwrap_err();
+        assert!(matches!(err.downcast_ref::<Error>(),
+                         Some(Error::MalformedPacket(_))));
+        assert!(buf.is_empty());
+
+        // The infallible serializer still works.
+        l.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), 5);
+
+        // Serializing a subpacket uses the checked serializer.
+        use crate::packet::signature::subpacket::{
+            NotationData, Subpacket, SubpacketValue,
+        };
+        let notation = |len| Subpacket::new(SubpacketValue::NotationData(
+            NotationData::new("a@example.org", vec![0; len], None)), false)
+            .unwrap();
+        // Tag, flags, name and value lengths, name, and value.
+        let overhead = 1 + 4 + 2 + 2 + "a@example.org".len();
+        let sp = notation(SubpacketArea::MAX_SIZE - 5 - overhead);
+        assert_eq!(sp.to_vec().unwrap().len(), SubpacketArea::MAX_SIZE);
+        let sp = notation(SubpacketArea::MAX_SIZE - 4 - overhead);
+        let err = sp.to_vec().unwrap_err();
+        assert!(matches!(err.downcast_ref::<Error>(),
+                         Some(Error::MalformedPacket(_))));
+    }
 }
@@ -1,11 +1,14 @@
 use std::fmt;
 use std::ops::{BitAnd, BitOr};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen};
 
+use crate::Error;
+use crate::Result;
 use crate::types::Bitfield;
 
 /// Describes how a key may be used, and stores additional information.
@@ -107,6 +110,77 @@ impl fmt::Debug for KeyFlags {
     }
 }
 
+/// Formats the key flags using the same letters as the `Debug`
+/// implementation.
+///
+/// The letters are `C` (certification), `S` (signing), `Et`
+/// (transport encryption), `Er` (storage encryption), `A`
+/// (authentication), `D` (split key), and `G` (group key).  Unknown
+/// flags and padding are not included.
+///
+/// # Examples
+///
+/// ```
+/// use sequoia_openpgp as openpgp;
+/// use openpgp::types::KeyFlags;
+///
+/// let kf = KeyFlags::empty().set_certification().set_signing();
+/// assert_eq!(kf.to_string(), "CS");
+/// assert_eq!("CS".parse::<KeyFlags>().unwrap(), kf);
+/// ```
+impl fmt::Display for KeyFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (set, letter) in [
+            (self.for_certification(), "C"),
+            (self.for_signing(), "S"),
+            (self.for_transport_encryption(), "Et"),
+            (self.for_storage_encryption(), "Er"),
+            (self.for_authentication(), "A"),
+            (self.is_split_key(), "D"),
+            (self.is_group_key(), "G"),
+        ].iter() {
+            if *set {
+                f.write_str(letter)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses key flags in the format produced by the `Display`
+/// implementation.
+///
+/// The letters may appear in any order.  An unknown letter is an
+/// error.
+impl FromStr for KeyFlags {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut kf = KeyFlags::empty();
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            kf = match c {
+                'C' => kf.set_certification(),
+                'S' => kf.set_signing(),
+                'E' => match chars.next() {
+                    Some('t') => kf.set_transport_encryption(),
+                    Some('r') => kf.set_storage_encryption(),
+                    _ => return Err(Error::InvalidArgument(format!(
+                        "Invalid key flags {:?}: expected Et or Er", s))
+                                    .into()),
+                },
+                'A' => kf.set_authentication(),
+                'D' => kf.set_split_key(),
+                'G' => kf.set_group_key(),
+                c => return Err(Error::InvalidArgument(format!(
+                    "Invalid key flags {:?}: unknown flag {:?}", s, c))
+                                .into()),
+            };
+        }
+        Ok(kf)
+    }
+}
+
 impl BitAnd for &KeyFlags {
     type Output = KeyFlags;
 
@@ -163,11 +237,95 @@ impl KeyFlags {
         KeyFlags::new(&[])
     }
 
+    /// Returns a new `KeyFlags` that only allows certifying other
+    /// keys.
+    ///
+    /// This is a shorthand for `KeyFlags::empty().set_certification()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// let kf = KeyFlags::certification();
+    /// assert!(kf.for_certification());
+    /// assert_eq!(kf.count_capabilities(), 1);
+    /// ```
+    pub fn certification() -> Self {
+        KeyFlags::empty().set_certification()
+    }
+
+    /// Returns a new `KeyFlags` that only allows signing data.
+    ///
+    /// This is a shorthand for `KeyFlags::empty().set_signing()`.
+    pub fn signing() -> Self {
+        KeyFlags::empty().set_signing()
+    }
+
+    /// Returns a new `KeyFlags` that only allows encrypting
+    /// communications.
+    ///
+    /// This is a shorthand for
+    /// `KeyFlags::empty().set_transport_encryption()`.
+    pub fn transport_encryption() -> Self {
+        KeyFlags::empty().set_transport_encryption()
+    }
+
+    /// Returns a new `KeyFlags` that only allows encrypting data at
+    /// rest.
+    ///
+    /// This is a shorthand for
+    /// `KeyFlags::empty().set_storage_encryption()`.
+    pub fn storage_encryption() -> Self {
+        KeyFlags::empty().set_storage_encryption()
+    }
+
+    /// Returns a new `KeyFlags` that only allows authentication.
+    ///
+    /// This is a shorthand for `KeyFlags::empty().set_authentication()`.
+    pub fn authentication() -> Self {
+        KeyFlags::empty().set_authentication()
+    }
+
     /// Returns a slice containing the raw values.
     pub(crate) fn as_slice(&self) -> &[u8] {
         self.0.as_slice()
     }
 
+    /// Creates a new instance from its serialized form.
+    ///
+    /// This is the inverse of [`KeyFlags::to_bytes`].  All bits,
+    /// including ones that Sequoia doesn't know about, and any
+    /// padding are preserved.
+    ///
+    ///   [`KeyFlags::to_bytes`]: KeyFlags::to_bytes()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// let kf = KeyFlags::from_bytes(&[0x03, 0x80]);
+    /// assert!(kf.for_certification());
+    /// assert!(kf.for_signing());
+    /// assert!(kf.get(15));
+    /// assert_eq!(kf.to_bytes(), vec![0x03, 0x80]);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::new(bytes)
+    }
+
+    /// Returns the serialized form.
+    ///
+    /// This is the body of a [Key Flags subpacket].
+    ///
+    ///   [Key Flags subpacket]: https://tools.ietf.org/html/rfc4880#section-5.2.3.21
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
     /// Compares two key flag sets for semantic equality.
     ///
     /// `KeyFlags`' implementation of `PartialEq` compares two key
@@ -194,6 +352,36 @@ impl KeyFlags {
         self.0.normalized_eq(&other.0)
     }
 
+    /// Returns whether all flags set in `other` are also set in
+    /// `self`.
+    ///
+    /// Like [`KeyFlags::normalized_eq`], this ignores padding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// let required = KeyFlags::empty().set_signing();
+    /// let kf = KeyFlags::empty().set_signing().set_certification();
+    ///
+    /// assert!(kf.contains(&required));
+    /// assert!(! required.contains(&kf));
+    /// assert!(kf.contains(&KeyFlags::empty()));
+    /// ```
+    pub fn contains(&self, other: &Self) -> bool {
+        (self & other).normalized_eq(other)
+    }
+
+    /// Returns whether all flags set in `self` are also set in
+    /// `other`.
+    ///
+    /// This is the inverse of [`KeyFlags::contains`].
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        other.contains(self)
+    }
+
     /// Returns whether the specified key flag is set.
     ///
     /// # Examples
@@ -327,6 +515,24 @@ impl KeyFlags {
         self.clear(KEY_FLAG_ENCRYPT_AT_REST)
     }
 
+    /// This key may be used to encrypt communications or storage.
+    ///
+    /// This is the same as `for_transport_encryption() ||
+    /// for_storage_encryption()`.
+    pub fn can_encrypt(&self) -> bool {
+        self.for_transport_encryption() || self.for_storage_encryption()
+    }
+
+    /// Sets or clears both the transport and the storage encryption
+    /// flags.
+    pub fn set_encrypt_both(self, encrypt: bool) -> Self {
+        if encrypt {
+            self.set_transport_encryption().set_storage_encryption()
+        } else {
+            self.clear_transport_encryption().clear_storage_encryption()
+        }
+    }
+
     /// This key may be used for authentication.
     pub fn for_authentication(&self) -> bool {
         self.get(KEY_FLAG_AUTHENTICATE)
@@ -382,6 +588,48 @@ impl KeyFlags {
     pub fn is_empty(&self) -> bool {
         self.as_slice().iter().all(|b| *b == 0)
     }
+
+    /// Returns a new `KeyFlags` with all flags cleared.
+    ///
+    /// Unlike [`KeyFlags::empty`], this preserves the length of the
+    /// serialized form, i.e., every byte, including any padding, is
+    /// set to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// let kf = KeyFlags::new(&[0x03, 0x80]).clear_all();
+    /// assert!(kf.is_empty());
+    /// assert_eq!(kf.to_bytes(), vec![0x00, 0x00]);
+    /// ```
+    pub fn clear_all(self) -> Self {
+        Self::new(vec![0; self.as_slice().len()])
+    }
+
+    /// Returns the number of known capabilities that are set.
+    ///
+    /// This counts the certification, signing, transport encryption,
+    /// storage encryption, authentication, split key, and group key
+    /// flags.  Unknown flags are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sequoia_openpgp as openpgp;
+    /// use openpgp::types::KeyFlags;
+    ///
+    /// let kf = KeyFlags::empty().set_certification().set_signing();
+    /// assert_eq!(kf.count_capabilities(), 2);
+    ///
+    /// // Bit 15 is not a known flag.
+    /// assert_eq!(kf.set(15).count_capabilities(), 2);
+    /// ```
+    pub fn count_capabilities(&self) -> usize {
+        KNOWN_KEY_FLAGS.iter().filter(|&&bit| self.get(bit)).count()
+    }
 }
 
 /// This key may be used to certify other keys.
@@ -407,6 +655,17 @@ const KEY_FLAG_AUTHENTICATE: usize = 5;
 /// than one person.
 const KEY_FLAG_GROUP_KEY: usize = 7;
 
+/// All key flags known to Sequoia.
+const KNOWN_KEY_FLAGS: [usize; 7] = [
+    KEY_FLAG_CERTIFY,
+    KEY_FLAG_SIGN,
+    KEY_FLAG_ENCRYPT_FOR_TRANSPORT,
+    KEY_FLAG_ENCRYPT_AT_REST,
+    KEY_FLAG_SPLIT_KEY,
+    KEY_FLAG_AUTHENTICATE,
+    KEY_FLAG_GROUP_KEY,
+];
+
 #[cfg(test)]
 impl Arbitrary for KeyFlags {
     fn arbitrary(g: &mut Gen) -> Self {
@@ -436,4 +695,180 @@ mod tests {
             true
         }
     }
+
+    #[test]
+    fn display_and_parse() {
+        for (kf, s) in [
+            (KeyFlags::empty(), ""),
+            (KeyFlags::empty().set_certification(), "C"),
+            (KeyFlags::empty().set_certification().set_signing(), "CS"),
+            (KeyFlags::empty().set_transport_encryption()
+             .set_storage_encryption(), "EtEr"),
+            (KeyFlags::empty().set_signing().set_authentication(), "SA"),
+            (KeyFlags::empty().set_certification().set_signing()
+             .set_transport_encryption().set_storage_encryption()
+             .set_authentication().set_split_key().set_group_key(),
+             "CSEtErADG"),
+        ].iter() {
+            assert_eq!(&kf.to_string(), s);
+            assert_eq!(&s.parse::<KeyFlags>().unwrap(), kf);
+        }
+
+        // Order doesn't matter.
+        assert_eq!("AEtC".parse::<KeyFlags>().unwrap(),
+                   KeyFlags::empty().set_certification()
+                   .set_transport_encryption().set_authentication());
+
+        assert!("CX".parse::<KeyFlags>().is_err());
+        assert!("E".parse::<KeyFlags>().is_err());
+        assert!("Ex".parse::<KeyFlags>().is_err());
+        assert!("c".parse::<KeyFlags>().is_err());
+    }
+
+    #[test]
+    fn can_encrypt() {
+        assert!(! KeyFlags::empty().can_encrypt());
+        assert!(! KeyFlags::empty().set_signing().set_certification()
+                .can_encrypt());
+        assert!(KeyFlags::empty().set_transport_encryption().can_encrypt());
+        assert!(KeyFlags::empty().set_storage_encryption().can_encrypt());
+
+        let kf = KeyFlags::empty().set_signing().set_encrypt_both(true);
+        assert!(kf.can_encrypt());
+        assert!(kf.for_transport_encryption());
+        assert!(kf.for_storage_encryption());
+        assert!(kf.for_signing());
+
+        let kf = kf.set_encrypt_both(false);
+        assert!(! kf.can_encrypt());
+        assert!(! kf.for_transport_encryption());
+        assert!(! kf.for_storage_encryption());
+        assert!(kf.for_signing());
+    }
+
+    #[test]
+    fn contains() {
+        let cs = KeyFlags::empty().set_certification().set_signing();
+        let sa = KeyFlags::empty().set_signing().set_authentication();
+        let e = KeyFlags::empty().set_encrypt_both(true);
+
+        assert!(cs.contains(&cs));
+        assert!(cs.contains(&KeyFlags::empty().set_signing()));
+        assert!(KeyFlags::empty().set_signing().is_subset_of(&cs));
+        assert!(cs.contains(&KeyFlags::empty()));
+        assert!(KeyFlags::empty().is_subset_of(&cs));
+
+        // Overlapping.
+        assert!(! cs.contains(&sa));
+        assert!(! sa.contains(&cs));
+        assert!(! cs.is_subset_of(&sa));
+
+        // Disjoint.
+        assert!(! cs.contains(&e));
+        assert!(! e.is_subset_of(&cs));
+
+        // Padding is ignored, but unknown flags are not.
+        assert!(KeyFlags::new(&[0x03]).contains(&KeyFlags::new(&[0x01, 0, 0])));
+        assert!(KeyFlags::new(&[0x01, 0, 0]).is_subset_of(&KeyFlags::new(&[0x03])));
+        assert!(! KeyFlags::new(&[0x03]).contains(&KeyFlags::new(&[0x01, 0x01])));
+    }
+
+    quickcheck! {
+        fn contains_union(a: KeyFlags, b: KeyFlags) -> bool {
+            let u = &a | &b;
+            u.contains(&a) && u.contains(&b)
+                && a.is_subset_of(&u) && b.is_subset_of(&u)
+        }
+    }
+
+    #[test]
+    fn clear_all() {
+        let kf = KeyFlags::empty().set_certification().set_signing()
+            .set_group_key().set(12);
+        let cleared = kf.clone().clear_all();
+        assert!(cleared.is_empty());
+        assert_eq!(cleared.count_capabilities(), 0);
+        assert_eq!(cleared.as_slice().len(), kf.as_slice().len());
+        assert!(cleared.normalized_eq(&KeyFlags::empty()));
+
+        // Padding is preserved.
+        assert_eq!(KeyFlags::new(&[0x01, 0x00, 0x00]).clear_all().to_bytes(),
+                   vec![0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn count_capabilities() {
+        assert_eq!(KeyFlags::empty().count_capabilities(), 0);
+        assert_eq!(KeyFlags::empty().set_signing().count_capabilities(), 1);
+        assert_eq!(KeyFlags::empty().set_encrypt_both(true)
+                   .set_authentication().count_capabilities(), 3);
+        assert_eq!(KeyFlags::empty().set_certification().set_signing()
+                   .set_transport_encryption().set_storage_encryption()
+                   .set_authentication().set_split_key().set_group_key()
+                   .count_capabilities(), 7);
+
+        // Unknown flags are not counted.
+        assert_eq!(KeyFlags::new(&[0x40, 0xff]).count_capabilities(), 0);
+        assert_eq!(KeyFlags::new(&[0xff, 0xff]).count_capabilities(), 7);
+    }
+
+    #[test]
+    fn serde_roundtrip() -> Result<()> {
+        for kf in [
+            KeyFlags::empty(),
+            KeyFlags::empty().set_certification().set_signing(),
+            KeyFlags::empty().set_encrypt_both(true).set_group_key(),
+            // Unknown flags and padding are preserved.
+            KeyFlags::new(&[0x40, 0x00, 0x01]),
+            KeyFlags::new(&[0x01, 0x00]),
+        ].iter() {
+            let json = serde_json::to_string(kf)?;
+            assert_eq!(&serde_json::from_str::<KeyFlags>(&json)?, kf);
+        }
+        Ok(())
+    }
+
+    quickcheck! {
+        fn serde_roundtrip_arbitrary(val: KeyFlags) -> bool {
+            let json = serde_json::to_vec(&val).unwrap();
+            serde_json::from_slice::<KeyFlags>(&json).unwrap() == val
+        }
+    }
+
+    quickcheck! {
+        fn display_roundtrip(val: KeyFlags) -> bool {
+            let kf: KeyFlags = val.to_string().parse().unwrap();
+            // Unknown flags and padding are lost.
+            kf.normalized_eq(&(&val & &KeyFlags::new(&[0xbf])))
+        }
+    }
+
+    #[test]
+    fn single_capability_constructors() {
+        for (kf, bit) in [
+            (KeyFlags::certification(), KEY_FLAG_CERTIFY),
+            (KeyFlags::signing(), KEY_FLAG_SIGN),
+            (KeyFlags::transport_encryption(),
+             KEY_FLAG_ENCRYPT_FOR_TRANSPORT),
+            (KeyFlags::storage_encryption(), KEY_FLAG_ENCRYPT_AT_REST),
+            (KeyFlags::authentication(), KEY_FLAG_AUTHENTICATE),
+        ].iter() {
+            assert_eq!(kf, &KeyFlags::empty().set(*bit));
+            assert_eq!(kf.count_capabilities(), 1);
+            assert_eq!(kf.to_bytes(), vec![1 << bit]);
+        }
+
+        assert_eq!(KeyFlags::signing(), KeyFlags::empty().set_signing());
+        assert!(KeyFlags::transport_encryption().can_encrypt());
+        assert!(! KeyFlags::transport_encryption().for_storage_encryption());
+        assert!(! KeyFlags::storage_encryption().for_transport_encryption());
+    }
+
+    quickcheck! {
+        fn bytes_roundtrip(bytes: Vec<u8>) -> bool {
+            let kf = KeyFlags::from_bytes(&bytes);
+            kf.to_bytes() == bytes
+                && KeyFlags::from_bytes(&kf.to_bytes()) == kf
+        }
+    }
 }
@@ -1910,6 +1910,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rfr_codes() {
+        use ReasonForRevocation::*;
+        for (code, rfr, typ) in [
+            (0, Unspecified, RevocationType::Hard),
+            (1, KeySuperseded, RevocationType::Soft),
+            (2, KeyCompromised, RevocationType::Hard),
+            (3, KeyRetired, RevocationType::Soft),
+            (32, UIDRetired, RevocationType::Soft),
+            (100, Private(100), RevocationType::Hard),
+            (4, Unknown(4), RevocationType::Hard),
+        ].iter().cloned() {
+            assert_eq!(ReasonForRevocation::from(code), rfr);
+            assert_eq!(u8::from(rfr), code);
+            assert_eq!(rfr.revocation_type(), typ);
+        }
+    }
+
     quickcheck! {
         fn df_roundtrip(df: DataFormat) -> bool {
             let val: u8 = df.into();
@@ -84,9 +84,12 @@ impl TryFrom<SystemTime> for Timestamp {
         match t.duration_since(std::time::UNIX_EPOCH) {
             Ok(d) if d.as_secs() <= std::u32::MAX as u64 =>
                 Ok(Timestamp(d.as_secs() as u32)),
-            _ => Err(Error::InvalidArgument(
+            Ok(_) => Err(Error::InvalidArgument(
                 format!("Time exceeds u32 epoch: {:?}", t))
                      .into()),
+            Err(_) => Err(Error::InvalidArgument(
+                format!("Time predates the Unix epoch: {:?}", t))
+                     .into()),
         }
     }
 }
@@ -866,6 +866,10 @@ impl PacketDumper {
                        .collect::<Vec<String>>().join(", "))?,
             IntendedRecipient(ref fp) =>
                 write!(output, "{}    Intended Recipient: {}", i, fp)?,
+            PreferredAEADCiphersuites(ref c) =>
+                write!(output, "{}    AEAD ciphersuite preferences: {}", i,
+                       c.iter().map(|(s, a)| format!("{:?}/{:?}", s, a))
+                       .collect::<Vec<String>>().join(", "))?,
             AttestedCertifications(digests) => {
                 write!(output, "{}    Attested Certifications:", i)?;
                 if digests.is_empty() {